@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ArchiveConfig {
+    /// The section whose pages are archived, as a path relative to the `content` directory
+    /// (eg `blog`). Defaults to the index section
+    pub section: String,
+    /// The path the archive pages are rendered at: `/{path}/{year}/` and `/{path}/{year}/{month}/`
+    pub path: String,
+    /// Whether to render a page per month in addition to the page per year
+    pub monthly: bool,
+    /// If this is set, the archive pages will be paginated
+    pub paginate_by: Option<usize>,
+    /// Path to use for pagination
+    pub paginate_path: String,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        ArchiveConfig {
+            section: String::new(),
+            path: "archive".to_string(),
+            monthly: true,
+            paginate_by: None,
+            paginate_path: "page".to_string(),
+        }
+    }
+}
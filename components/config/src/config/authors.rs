@@ -0,0 +1,18 @@
+use std::collections::HashMap;
+
+use libs::toml::Value as Toml;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthorConfig {
+    /// The name to display, defaults to the author id
+    pub name: Option<String>,
+    pub email: Option<String>,
+    /// A short biography, rendered as Markdown with the `markdown` filter if needed
+    pub bio: Option<String>,
+    /// The path or URL to a picture of the author
+    pub avatar: Option<String>,
+    /// Any other user defined data, eg links to social profiles
+    pub extra: HashMap<String, Toml>,
+}
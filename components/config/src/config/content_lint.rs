@@ -0,0 +1,114 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// What a content lint rule does when it finds something
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Off,
+    Warn,
+    Error,
+}
+
+/// The rules checked on the content of pages and sections by `zola check`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    /// A heading more than one level below the previous one, e.g. a `###` after a `#`
+    SkippedHeadingLevel,
+    /// More than one `#` heading
+    MultipleH1,
+    /// An image without alt text
+    MissingAltText,
+    /// A link without text or without destination
+    EmptyLink,
+    /// Several headings with the same id
+    DuplicateAnchor,
+    /// A `title` longer than `max_title_length`
+    LongTitle,
+    /// A `description` longer than `max_description_length`
+    LongDescription,
+    /// A URL in the text that is not a link
+    BareUrl,
+}
+
+impl LintRule {
+    pub fn name(self) -> &'static str {
+        match self {
+            LintRule::SkippedHeadingLevel => "skipped_heading_level",
+            LintRule::MultipleH1 => "multiple_h1",
+            LintRule::MissingAltText => "missing_alt_text",
+            LintRule::EmptyLink => "empty_link",
+            LintRule::DuplicateAnchor => "duplicate_anchor",
+            LintRule::LongTitle => "long_title",
+            LintRule::LongDescription => "long_description",
+            LintRule::BareUrl => "bare_url",
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The level of each content lint rule of `zola check`, which pages can ignore with `lint_ignore`.
+/// All the rules are off unless enabled.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContentLint {
+    /// Emit "off", "warn" or "error" for headings skipping a level
+    pub skipped_heading_level: LintLevel,
+    /// Emit "off", "warn" or "error" for content with more than one `h1`
+    pub multiple_h1: LintLevel,
+    /// Emit "off", "warn" or "error" for images without alt text
+    pub missing_alt_text: LintLevel,
+    /// Emit "off", "warn" or "error" for links without text or destination
+    pub empty_link: LintLevel,
+    /// Emit "off", "warn" or "error" for headings sharing an id
+    pub duplicate_anchor: LintLevel,
+    /// Emit "off", "warn" or "error" for titles longer than `max_title_length`
+    pub long_title: LintLevel,
+    /// The maximum number of characters of a title
+    pub max_title_length: usize,
+    /// Emit "off", "warn" or "error" for descriptions longer than `max_description_length`
+    pub long_description: LintLevel,
+    /// The maximum number of characters of a description
+    pub max_description_length: usize,
+    /// Emit "off", "warn" or "error" for URLs in the text that are not links
+    pub bare_url: LintLevel,
+}
+
+impl ContentLint {
+    pub fn level(&self, rule: LintRule) -> LintLevel {
+        match rule {
+            LintRule::SkippedHeadingLevel => self.skipped_heading_level,
+            LintRule::MultipleH1 => self.multiple_h1,
+            LintRule::MissingAltText => self.missing_alt_text,
+            LintRule::EmptyLink => self.empty_link,
+            LintRule::DuplicateAnchor => self.duplicate_anchor,
+            LintRule::LongTitle => self.long_title,
+            LintRule::LongDescription => self.long_description,
+            LintRule::BareUrl => self.bare_url,
+        }
+    }
+}
+
+impl Default for ContentLint {
+    fn default() -> Self {
+        ContentLint {
+            skipped_heading_level: LintLevel::Off,
+            multiple_h1: LintLevel::Off,
+            missing_alt_text: LintLevel::Off,
+            empty_link: LintLevel::Off,
+            duplicate_anchor: LintLevel::Off,
+            long_title: LintLevel::Off,
+            max_title_length: 70,
+            long_description: LintLevel::Off,
+            max_description_length: 160,
+            bare_url: LintLevel::Off,
+        }
+    }
+}
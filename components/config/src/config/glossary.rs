@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
+
+use errors::{bail, Context, Result};
+use libs::regex::{escape, Regex};
+use libs::toml;
+use serde::{Deserialize, Serialize};
+use utils::fs::read_file;
+
+/// A term of the glossary, linked to `link` and/or wrapped in `<abbr title="...">` on first use
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GlossaryTerm {
+    /// The text to look for, case-sensitive
+    pub term: String,
+    /// The expansion or short definition of the term
+    pub title: Option<String>,
+    /// Where the term links to, either an `@/` internal link or a URL
+    pub link: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GlossaryFile {
+    #[serde(default)]
+    terms: Vec<GlossaryTerm>,
+}
+
+/// The glossary terms of a language
+#[derive(Clone, Debug)]
+pub struct Glossary {
+    pub terms: Vec<GlossaryTerm>,
+    /// Matches any of the terms, the longest ones first
+    regex: Regex,
+    /// term -> its index in `terms`
+    indices: HashMap<String, usize>,
+}
+
+impl Glossary {
+    pub fn new(terms: Vec<GlossaryTerm>) -> Result<Glossary> {
+        let mut indices = HashMap::new();
+        for (i, term) in terms.iter().enumerate() {
+            if term.term.trim().is_empty() {
+                bail!("A glossary term cannot be empty");
+            }
+            if term.title.is_none() && term.link.is_none() {
+                bail!("The glossary term `{}` needs a `title`, a `link` or both", term.term);
+            }
+            if indices.insert(term.term.clone(), i).is_some() {
+                bail!("The glossary term `{}` is defined more than once", term.term);
+            }
+        }
+
+        let mut sorted: Vec<_> = terms.iter().map(|t| t.term.as_str()).collect();
+        sorted.sort_by_key(|t| std::cmp::Reverse(t.len()));
+        let pattern = sorted.into_iter().map(escape).collect::<Vec<_>>().join("|");
+        let regex = Regex::new(&pattern)?;
+
+        Ok(Glossary { terms, regex, indices })
+    }
+
+    /// Loads the terms from a TOML file containing an array of `[[terms]]`
+    pub fn from_file(path: &Path) -> Result<Glossary> {
+        let content = read_file(path)?;
+        let file: GlossaryFile = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse glossary {}", path.display()))?;
+        Glossary::new(file.terms).with_context(|| format!("Invalid glossary {}", path.display()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Finds the first term in `text` that appears as a whole word and is not skipped.
+    /// Returns where it is in `text` and its index in `terms`.
+    pub fn find(&self, text: &str, skip: impl Fn(usize) -> bool) -> Option<(Range<usize>, usize)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.regex.find_iter(text).find_map(|m| {
+            let before = text[..m.start()].chars().next_back();
+            let after = text[m.end()..].chars().next();
+            if before.is_some_and(char::is_alphanumeric) || after.is_some_and(char::is_alphanumeric)
+            {
+                return None;
+            }
+            let idx = self.indices[m.as_str()];
+            if skip(idx) {
+                None
+            } else {
+                Some((m.range(), idx))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(term: &str) -> GlossaryTerm {
+        GlossaryTerm { term: term.to_string(), title: Some("title".to_string()), link: None }
+    }
+
+    #[test]
+    fn can_find_whole_word_terms() {
+        let glossary = Glossary::new(vec![term("SSG"), term("static site"), term("C++")]).unwrap();
+        assert_eq!(glossary.find("SSGs are an SSG", |_| false), Some((12..15, 0)));
+        assert_eq!(glossary.find("a static site, C++", |_| false), Some((2..13, 1)));
+        assert_eq!(glossary.find("a static site, C++", |i| i == 1), Some((15..18, 2)));
+        assert_eq!(glossary.find("ssg", |_| false), None);
+    }
+
+    #[test]
+    fn errors_on_invalid_terms() {
+        let no_title = GlossaryTerm { term: "SSG".to_string(), title: None, link: None };
+        assert!(Glossary::new(vec![no_title]).is_err());
+        assert!(Glossary::new(vec![term("SSG"), term("SSG")]).is_err());
+        assert!(Glossary::new(vec![term(" ")]).is_err());
+    }
+}
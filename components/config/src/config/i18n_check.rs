@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::link_checker::LinkCheckerLevel;
+
+/// What `zola check` reports about the translations of a multilingual site
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct I18nCheck {
+    /// Emit either "error" or "warn" for pages and sections not translated in every language.
+    pub missing_content: LinkCheckerLevel,
+    /// Emit either "error" or "warn" for `translations` keys defined in some languages only.
+    pub missing_keys: LinkCheckerLevel,
+    /// Emit either "error" or "warn" for keys used with `trans` in templates but defined in no language.
+    pub undefined_keys: LinkCheckerLevel,
+}
+
+impl Default for I18nCheck {
+    fn default() -> Self {
+        I18nCheck {
+            missing_content: LinkCheckerLevel::Warn,
+            missing_keys: LinkCheckerLevel::Warn,
+            undefined_keys: LinkCheckerLevel::Error,
+        }
+    }
+}
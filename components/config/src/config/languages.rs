@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::config::search;
 use crate::config::taxonomies;
+use crate::config::translations;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default, deny_unknown_fields)]
@@ -14,6 +15,9 @@ pub struct LanguageOptions {
     pub title: Option<String>,
     /// Description of the site. Defaults to None
     pub description: Option<String>,
+    /// The URL the language is served at, if not under `/{code}/` of the site `base_url`.
+    /// Defaults to None
+    pub base_url: Option<String>,
     /// Whether to generate feeds for that language, defaults to `false`
     pub generate_feeds: bool,
     /// The filenames to use for feeds. Used to find the templates, too.
@@ -25,9 +29,12 @@ pub struct LanguageOptions {
     /// The search config, telling what to include in the search index for that language
     pub search: search::Search,
     /// A toml crate `Table` with String key representing term and value
-    /// another `String` representing its translation.
+    /// its translation, either a `String` or a table of plural forms.
     /// Use `get_translation()` method for translating key into different languages.
-    pub translations: HashMap<String, String>,
+    pub translations: HashMap<String, translations::Translation>,
+    /// Whether pages of the default language without a translation in that language are
+    /// rendered in it anyway, with `page.is_fallback` set. Defaults to `false`
+    pub translation_fallback: bool,
 }
 
 impl LanguageOptions {
@@ -65,6 +72,7 @@ impl LanguageOptions {
         }
         merge_field!(self.title, other.title, "title");
         merge_field!(self.description, other.description, "description");
+        merge_field!(self.base_url, other.base_url, "base_url");
         merge_field!(
             self.feed_filenames.is_empty()
                 || self.feed_filenames == LanguageOptions::default().feed_filenames,
@@ -82,6 +90,7 @@ impl LanguageOptions {
 
         self.generate_feeds = self.generate_feeds || other.generate_feeds;
         self.build_search_index = self.build_search_index || other.build_search_index;
+        self.translation_fallback = self.translation_fallback || other.translation_fallback;
 
         if self.search == search::Search::default() {
             self.search = other.search.clone();
@@ -102,12 +111,14 @@ impl Default for LanguageOptions {
         LanguageOptions {
             title: None,
             description: None,
+            base_url: None,
             generate_feeds: false,
             feed_filenames: vec!["atom.xml".to_string()],
             taxonomies: vec![],
             build_search_index: false,
             search: search::Search::default(),
             translations: HashMap::new(),
+            translation_fallback: false,
         }
     }
 }
@@ -130,23 +141,27 @@ mod tests {
         let mut base_default_language_options = LanguageOptions {
             title: Some("Site's title".to_string()),
             description: None,
+            base_url: None,
             generate_feeds: true,
             feed_filenames: vec!["atom.xml".to_string()],
             taxonomies: vec![],
             build_search_index: true,
             search: search::Search::default(),
             translations: HashMap::new(),
+            translation_fallback: false,
         };
 
         let section_default_language_options = LanguageOptions {
             title: None,
             description: Some("Site's description".to_string()),
+            base_url: None,
             generate_feeds: false,
             feed_filenames: vec!["rss.xml".to_string()],
             taxonomies: vec![],
             build_search_index: true,
             search: search::Search::default(),
             translations: HashMap::new(),
+            translation_fallback: false,
         };
 
         base_default_language_options.merge(&section_default_language_options).unwrap();
@@ -157,23 +172,27 @@ mod tests {
         let mut base_default_language_options = LanguageOptions {
             title: Some("Site's title".to_string()),
             description: Some("Duplicate site description".to_string()),
+            base_url: None,
             generate_feeds: true,
             feed_filenames: vec![],
             taxonomies: vec![],
             build_search_index: true,
             search: search::Search::default(),
             translations: HashMap::new(),
+            translation_fallback: false,
         };
 
         let section_default_language_options = LanguageOptions {
             title: None,
             description: Some("Site's description".to_string()),
+            base_url: None,
             generate_feeds: false,
             feed_filenames: vec!["Some feed_filename".to_string()],
             taxonomies: vec![],
             build_search_index: true,
             search: search::Search::default(),
             translations: HashMap::new(),
+            translation_fallback: false,
         };
 
         let res =
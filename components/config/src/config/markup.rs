@@ -1,5 +1,7 @@
-use std::{path::Path, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
+use libs::hypher;
+use libs::regex::Regex;
 use libs::syntect::{
     highlighting::{Theme, ThemeSet},
     html::css_for_theme_with_class_style,
@@ -7,10 +9,11 @@ use libs::syntect::{
 };
 use serde::{Deserialize, Serialize};
 
-use errors::{bail, Result};
-use utils::types::InsertAnchor;
+use errors::{bail, Context, Result};
+use utils::types::{AutoSummary, InsertAnchor};
 
-use crate::highlighting::{CLASS_STYLE, THEME_SET};
+use crate::config::glossary::Glossary;
+use crate::highlighting::{ansi_css, CLASS_STYLE, THEME_SET};
 
 pub const DEFAULT_HIGHLIGHT_THEME: &str = "base16-ocean-dark";
 
@@ -23,6 +26,18 @@ pub struct ThemeCss {
     pub filename: String,
 }
 
+/// The library used to highlight code
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Highlighter {
+    /// Sublime syntaxes through syntect
+    #[default]
+    Syntect,
+    /// The grammars bundled when Zola is built with the `tree-sitter` feature, falling back to
+    /// syntect for the other languages
+    TreeSitter,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Markdown {
@@ -52,6 +67,36 @@ pub struct Markdown {
     pub definition_list: bool,
     /// Whether footnotes are rendered at the bottom in the style of GitHub.
     pub bottom_footnotes: bool,
+    /// Whether footnotes are rendered next to their reference as sidenotes, with the
+    /// `sidenote.html` template. Takes precedence over `bottom_footnotes`.
+    pub sidenotes: bool,
+    /// Whether `{#id .class key=value}` attributes can be set on images, links, paragraphs,
+    /// tables and fenced code blocks, like on headings. Defaults to false
+    pub attributes: bool,
+    /// Where the glossary terms come from: a TOML file relative to the site root, with
+    /// `file.{lang}.toml` files for the other languages, or a `@/` path to a section whose pages
+    /// are the terms
+    pub glossary: Option<String>,
+    /// The loaded glossaries by language
+    #[serde(skip_serializing, skip_deserializing)] // not a typo, 2 are need
+    pub glossaries: HashMap<String, Glossary>,
+    /// The languages in which soft hyphens are inserted in words, using embedded hyphenation
+    /// patterns. Defaults to none
+    pub hyphenate: Vec<String>,
+    /// A regex matching the callout markers in code blocks, e.g. `// \((\d+)\)`, whose first
+    /// group is the number of the callout. Defaults to none, disabling callouts
+    pub code_callouts: Option<String>,
+    /// Which library highlights the code. Defaults to syntect
+    pub highlighter: Highlighter,
+    /// Whether inline code with a language, like `` `let x = 5;`{:rust} ``, is highlighted.
+    /// Defaults to false
+    pub highlight_inline_code: bool,
+    /// A prefix to give the language at the start of inline code instead, like `#!` for
+    /// `` `#!rust let x = 5;` ``. Defaults to none
+    pub inline_code_prefix: Option<String>,
+    /// The compiled `code_callouts` regex
+    #[serde(skip_serializing, skip_deserializing)] // not a typo, 2 are need
+    pub code_callouts_regex: Option<Regex>,
     /// A list of directories to search for additional `.sublime-syntax` and `.tmTheme` files in.
     pub extra_syntaxes_and_themes: Vec<String>,
     /// The compiled extra syntaxes into a syntax set
@@ -65,8 +110,14 @@ pub struct Markdown {
     /// Whether to insert a link for each header like the ones you can see in this site if you hover one
     /// The default template can be overridden by creating a `anchor-link.html` in the `templates` directory
     pub insert_anchor_links: InsertAnchor,
+    /// How to generate the summary of pages without a `<!-- more -->` marker.
+    /// Defaults to `none`, can be overridden per section
+    pub auto_summary: AutoSummary,
     /// Whether to enable GitHub-style alerts
     pub github_alerts: bool,
+    /// Whether `[[Title]]` and `[[Title|label]]` links to pages and sections are enabled.
+    /// Defaults to false
+    pub wiki_links: bool,
 }
 
 impl Markdown {
@@ -80,6 +131,33 @@ impl Markdown {
         Ok(())
     }
 
+    /// Compiles the `code_callouts` pattern, which needs a group for the number of the callout
+    pub fn init_code_callouts(&mut self) -> Result<()> {
+        self.code_callouts_regex = match self.code_callouts {
+            Some(ref pattern) => {
+                let regex = Regex::new(pattern).with_context(|| {
+                    format!("Invalid `markdown.code_callouts` pattern `{}`", pattern)
+                })?;
+                if regex.captures_len() < 2 {
+                    bail!(
+                        "The `markdown.code_callouts` pattern `{}` needs a group matching the number of the callout",
+                        pattern
+                    );
+                }
+                Some(regex)
+            }
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// Gets the hyphenation patterns of a language, looked up by its primary subtag
+    pub fn hyphenation_lang(lang: &str) -> Option<hypher::Lang> {
+        let primary = lang.split(['-', '_']).next().unwrap_or(lang);
+        let code: [u8; 2] = primary.as_bytes().try_into().ok()?;
+        hypher::Lang::from_iso(code)
+    }
+
     /// Gets the configured highlight theme from the THEME_SET or the config's extra_theme_set
     /// Returns None if the configured highlighting theme is set to use css
     pub fn get_highlight_theme(&self) -> Option<&Theme> {
@@ -123,8 +201,9 @@ impl Markdown {
 
     pub fn export_theme_css(&self, theme_name: &str) -> Result<String> {
         if let Some(theme) = self.get_highlight_theme_by_name(theme_name) {
-            Ok(css_for_theme_with_class_style(theme, CLASS_STYLE)
-                .expect("the function can't even error?"))
+            let css = css_for_theme_with_class_style(theme, CLASS_STYLE)
+                .expect("the function can't even error?");
+            Ok(format!("{}\n{}", css, ansi_css()))
         } else {
             bail!("Theme {} not found", theme_name)
         }
@@ -237,12 +316,24 @@ impl Default for Markdown {
             smart_punctuation: false,
             definition_list: false,
             bottom_footnotes: false,
+            sidenotes: false,
+            attributes: false,
+            glossary: None,
+            glossaries: HashMap::new(),
+            hyphenate: Vec::new(),
+            code_callouts: None,
+            code_callouts_regex: None,
+            highlighter: Highlighter::Syntect,
+            highlight_inline_code: false,
+            inline_code_prefix: None,
             extra_syntaxes_and_themes: vec![],
             extra_syntax_set: None,
             extra_theme_set: Arc::new(None),
             lazy_async_image: false,
             insert_anchor_links: InsertAnchor::None,
+            auto_summary: AutoSummary::None,
             github_alerts: false,
+            wiki_links: false,
         }
     }
 }
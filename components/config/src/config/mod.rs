@@ -1,9 +1,16 @@
+pub mod archive;
+pub mod authors;
+pub mod content_lint;
+pub mod glossary;
+pub mod i18n_check;
 pub mod languages;
 pub mod link_checker;
 pub mod markup;
 pub mod search;
 pub mod slugify;
+pub mod spellcheck;
 pub mod taxonomies;
+pub mod translations;
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -13,13 +20,15 @@ use libs::toml::Value as Toml;
 use serde::{Deserialize, Serialize};
 
 use crate::theme::Theme;
-use errors::{anyhow, bail, Result};
+use errors::{anyhow, bail, Context, Result};
 use utils::fs::read_file;
 use utils::globs::build_ignore_glob_set;
 use utils::slugs::slugify_paths;
 
 // We want a default base url for tests
 const DEFAULT_BASE_URL: &str = "http://a-website.com";
+/// Where the author profiles are rendered
+pub const AUTHORS_PATH: &str = "authors";
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -54,7 +63,7 @@ pub struct Config {
     /// The list of supported languages outside of the default one
     pub languages: HashMap<String, languages::LanguageOptions>,
     /// The translations strings for the default language
-    translations: HashMap<String, String>,
+    translations: HashMap<String, translations::Translation>,
 
     /// Whether to generate feeds. Defaults to false.
     pub generate_feeds: bool,
@@ -68,6 +77,9 @@ pub struct Config {
     pub taxonomies: Vec<taxonomies::TaxonomyConfig>,
     /// The default author for pages.
     pub author: Option<String>,
+    /// The author profiles, keyed by the id used in the pages `authors` front matter.
+    /// Each of them gets a page listing their content if there is at least one.
+    pub authors: HashMap<String, authors::AuthorConfig>,
 
     /// Whether to compile the `sass` directory and output the css files into the static folder
     pub compile_sass: bool,
@@ -97,6 +109,12 @@ pub struct Config {
     pub preserve_dotfiles_in_output: bool,
 
     pub link_checker: link_checker::LinkChecker,
+    /// What `zola check` reports about missing translations
+    pub i18n_check: i18n_check::I18nCheck,
+    /// The content lint rules of `zola check`
+    pub content_lint: content_lint::ContentLint,
+    /// The dictionaries and word list of `zola check --spelling`
+    pub spellcheck: spellcheck::Spellcheck,
     /// The setup for which slugification strategies to use for paths, taxonomies and anchors
     pub slugify: slugify::Slugify,
     /// The search config, telling what to include in the search index
@@ -111,6 +129,13 @@ pub struct Config {
     pub generate_robots_txt: bool,
     /// Whether to exclude paginated pages in sitemap; can take values "none", "all"
     pub exclude_paginated_pages_in_sitemap: ExcludePaginatedPagesInSitemap,
+    /// Whether to read the local git history to fill the pages `date`/`updated` and `page.git`
+    pub use_git_history: bool,
+    /// The permalink pattern used for the paths of the pages, eg `/:year/:month/:slug/`.
+    /// Sections can override it with `page_permalink` in their front matter
+    pub page_permalink: Option<String>,
+    /// Generates yearly and monthly archive pages for the pages of a section if set
+    pub archive: Option<archive::ArchiveConfig>,
 }
 
 #[derive(Serialize)]
@@ -126,6 +151,7 @@ pub struct SerializedConfig<'a> {
     feed_filenames: &'a [String],
     taxonomies: &'a [taxonomies::TaxonomyConfig],
     author: &'a Option<String>,
+    authors: &'a HashMap<String, authors::AuthorConfig>,
     build_search_index: bool,
     extra: &'a HashMap<String, Toml>,
     markdown: &'a markup::Markdown,
@@ -155,7 +181,58 @@ impl Config {
         }
 
         config.add_default_language()?;
+        for (code, options) in &config.languages {
+            if let Some(ref base_url) = options.base_url {
+                if code == &config.default_language {
+                    bail!(
+                        "The default language `{}` cannot set its own `base_url`, use the top-level `base_url` instead",
+                        code
+                    );
+                }
+                if base_url.is_empty() {
+                    bail!("The `base_url` of the language `{}` cannot be empty", code);
+                }
+            }
+        }
         config.slugify_taxonomies();
+        if !config.authors.is_empty() {
+            for options in config.languages.values() {
+                if options.taxonomies.iter().any(|t| t.slug == AUTHORS_PATH) {
+                    bail!(
+                        "A taxonomy is using the `{}` path, which is reserved for the author profiles when `[authors]` is set",
+                        AUTHORS_PATH
+                    );
+                }
+            }
+        }
+        if let Some(ref mut archive) = config.archive {
+            archive.section = archive.section.trim_matches('/').to_string();
+            archive.path = archive.path.trim_matches('/').to_string();
+            if archive.path.is_empty() {
+                bail!("The `path` of `[archive]` cannot be empty");
+            }
+            if archive.paginate_by == Some(0) {
+                bail!("The `paginate_by` of `[archive]` needs to be greater than 0");
+            }
+        }
+        for lang in &config.markdown.hyphenate {
+            if !config.languages.contains_key(lang) {
+                bail!(
+                    "The language `{}` in `markdown.hyphenate` is not a language of the site",
+                    lang
+                );
+            }
+            if markup::Markdown::hyphenation_lang(lang).is_none() {
+                bail!("There are no hyphenation patterns for the language `{}`", lang);
+            }
+        }
+        #[cfg(not(feature = "tree-sitter"))]
+        if config.markdown.highlighter == markup::Highlighter::TreeSitter {
+            bail!(
+                "`markdown.highlighter` is set to \"tree-sitter\" but Zola was built without the `tree-sitter` feature"
+            );
+        }
+        config.markdown.init_code_callouts()?;
         config.link_checker.resolve_globset()?;
 
         let content_glob_set = build_ignore_glob_set(&config.ignored_content, "content")?;
@@ -199,8 +276,43 @@ impl Config {
         }
     }
 
+    /// Returns the base URL of the given language: its own one if set, the site one otherwise
+    pub fn base_url_for_lang(&self, lang: &str) -> &str {
+        self.languages.get(lang).and_then(|l| l.base_url.as_deref()).unwrap_or(&self.base_url)
+    }
+
+    /// The languages served at their own base URL, with that URL
+    pub fn languages_with_base_url(&self) -> Vec<(&str, &str)> {
+        let mut languages: Vec<_> = self
+            .languages
+            .iter()
+            .filter_map(|(code, l)| l.base_url.as_deref().map(|url| (code.as_str(), url)))
+            .collect();
+        languages.sort();
+        languages
+    }
+
+    /// Paths of languages served at their own base URL start with the language code.
+    /// That prefix is replaced by the base URL of the language.
+    fn split_base_url<'a>(&'a self, path: &'a str) -> (&'a str, &'a str) {
+        let trimmed = path.strip_prefix('/').unwrap_or(path);
+        for (code, options) in &self.languages {
+            let Some(ref base_url) = options.base_url else { continue };
+            if let Some(rest) = trimmed.strip_prefix(code.as_str()) {
+                if rest.is_empty() {
+                    return (base_url, "/");
+                }
+                if rest.starts_with('/') {
+                    return (base_url, rest);
+                }
+            }
+        }
+        (&self.base_url, path)
+    }
+
     /// Makes a url, taking into account that the base url might have a trailing slash
     pub fn make_permalink(&self, path: &str) -> String {
+        let (base_url, path) = self.split_base_url(path);
         let trailing_bit = if path.ends_with('/')
             || self.feed_filenames.iter().any(|feed_filename| path.ends_with(feed_filename))
             || path.is_empty()
@@ -212,17 +324,25 @@ impl Config {
         };
 
         // Index section with a base url that has a trailing slash
-        if self.base_url.ends_with('/') && path == "/" {
-            self.base_url.clone()
+        if base_url.ends_with('/') && path == "/" {
+            base_url.to_string()
         } else if path == "/" {
             // index section with a base url that doesn't have a trailing slash
-            format!("{}/", self.base_url)
-        } else if self.base_url.ends_with('/') && path.starts_with('/') {
-            format!("{}{}{}", self.base_url, &path[1..], trailing_bit)
-        } else if self.base_url.ends_with('/') || path.starts_with('/') {
-            format!("{}{}{}", self.base_url, path, trailing_bit)
+            format!("{}/", base_url)
+        } else if base_url.ends_with('/') && path.starts_with('/') {
+            format!("{}{}{}", base_url, &path[1..], trailing_bit)
+        } else if base_url.ends_with('/') || path.starts_with('/') {
+            format!("{}{}{}", base_url, path, trailing_bit)
         } else {
-            format!("{}/{}{}", self.base_url, path, trailing_bit)
+            format!("{}/{}{}", base_url, path, trailing_bit)
+        }
+    }
+
+    /// Makes all the languages served under the site base URL, for example when
+    /// it is overridden by `zola serve`
+    pub fn clear_languages_base_url(&mut self) {
+        for options in self.languages.values_mut() {
+            options.base_url = None;
         }
     }
 
@@ -233,12 +353,14 @@ impl Config {
         let mut base_language_options = languages::LanguageOptions {
             title: self.title.clone(),
             description: self.description.clone(),
+            base_url: None,
             generate_feeds: self.generate_feeds,
             feed_filenames: self.feed_filenames.clone(),
             build_search_index: self.build_search_index,
             taxonomies: self.taxonomies.clone(),
             search: self.search.clone(),
             translations: self.translations.clone(),
+            translation_fallback: false,
         };
 
         if let Some(section_language_options) = self.languages.get(&self.default_language) {
@@ -314,14 +436,28 @@ impl Config {
     }
 
     pub fn get_translation(&self, lang: &str, key: &str) -> Result<String> {
+        self.get_translation_with_args(lang, key, &HashMap::new())
+    }
+
+    /// Translates the key, picking the plural form from the `count` argument if the message
+    /// has some and replacing the `{name}` placeholders with the arguments.
+    pub fn get_translation_with_args(
+        &self,
+        lang: &str,
+        key: &str,
+        args: &HashMap<String, String>,
+    ) -> Result<String> {
         if let Some(options) = self.languages.get(lang) {
             options
                 .translations
                 .get(key)
                 .ok_or_else(|| {
                     anyhow!("Translation key '{}' for language '{}' is missing", key, lang)
+                })?
+                .format(lang, args)
+                .with_context(|| {
+                    format!("Failed to format translation key '{}' for language '{}'", key, lang)
                 })
-                .map(|term| term.to_string())
         } else {
             bail!("Language '{}' not found.", lang)
         }
@@ -339,7 +475,7 @@ impl Config {
         let options = &self.languages[lang];
 
         SerializedConfig {
-            base_url: &self.base_url,
+            base_url: self.base_url_for_lang(lang),
             mode: self.mode,
             title: &options.title,
             description: &options.description,
@@ -350,6 +486,7 @@ impl Config {
             feed_filenames: &options.feed_filenames,
             taxonomies: &options.taxonomies,
             author: &self.author,
+            authors: &self.authors,
             build_search_index: options.build_search_index,
             extra: &self.extra,
             markdown: &self.markdown,
@@ -404,6 +541,7 @@ impl Default for Config {
             hard_link_static: false,
             taxonomies: Vec::new(),
             author: None,
+            authors: HashMap::new(),
             compile_sass: false,
             minify_html: false,
             mode: Mode::Build,
@@ -416,6 +554,9 @@ impl Default for Config {
             output_dir: "public".to_string(),
             preserve_dotfiles_in_output: false,
             link_checker: link_checker::LinkChecker::default(),
+            i18n_check: i18n_check::I18nCheck::default(),
+            content_lint: content_lint::ContentLint::default(),
+            spellcheck: spellcheck::Spellcheck::default(),
             slugify: slugify::Slugify::default(),
             search: search::Search::default(),
             markdown: markup::Markdown::default(),
@@ -423,6 +564,9 @@ impl Default for Config {
             generate_sitemap: true,
             generate_robots_txt: true,
             exclude_paginated_pages_in_sitemap: ExcludePaginatedPagesInSitemap::None,
+            use_git_history: false,
+            page_permalink: None,
+            archive: None,
         }
     }
 }
@@ -460,12 +604,14 @@ mod tests {
             languages::LanguageOptions {
                 title: None,
                 description: description_lang_section.clone(),
+                base_url: None,
                 generate_feeds: true,
                 feed_filenames: config.feed_filenames.clone(),
                 taxonomies: config.taxonomies.clone(),
                 build_search_index: false,
                 search: search::Search::default(),
                 translations: config.translations.clone(),
+                translation_fallback: false,
             },
         );
         config.add_default_language().unwrap();
@@ -488,12 +634,14 @@ mod tests {
             languages::LanguageOptions {
                 title: title_lang_section.clone(),
                 description: None,
+                base_url: None,
                 generate_feeds: true,
                 feed_filenames: config.feed_filenames.clone(),
                 taxonomies: config.taxonomies.clone(),
                 build_search_index: false,
                 search: search::Search::default(),
                 translations: config.translations.clone(),
+                translation_fallback: false,
             },
         );
         let result = config.add_default_language();
@@ -578,6 +726,40 @@ hello = "world"
         assert_eq!(config.make_permalink("/tags/rust"), "http://127.0.0.1:1111/tags/rust/");
     }
 
+    #[test]
+    fn can_make_url_with_language_base_url() {
+        let config_str = r#"
+base_url = "https://example.com"
+default_language = "en"
+
+[languages.fr]
+base_url = "https://example.fr"
+
+[languages.fr-CA]
+"#;
+        let config = Config::parse(config_str).unwrap();
+        assert_eq!(config.make_permalink("/blog/"), "https://example.com/blog/");
+        assert_eq!(config.make_permalink("/fr/blog/"), "https://example.fr/blog/");
+        assert_eq!(config.make_permalink("fr"), "https://example.fr/");
+        assert_eq!(config.make_permalink("/fr/atom.xml"), "https://example.fr/atom.xml");
+        assert_eq!(config.make_permalink("/fr-CA/blog/"), "https://example.com/fr-CA/blog/");
+        assert_eq!(config.make_permalink("/french/"), "https://example.com/french/");
+        assert_eq!(config.serialize("fr").base_url, "https://example.fr");
+    }
+
+    #[test]
+    fn errors_on_default_language_base_url() {
+        let config_str = r#"
+base_url = "https://example.com"
+default_language = "en"
+
+[languages.en]
+base_url = "https://example.org"
+"#;
+        let err = Config::parse(config_str).unwrap_err();
+        assert!(err.to_string().contains("cannot set its own `base_url`"));
+    }
+
     // https://github.com/Keats/gutenberg/issues/486
     #[test]
     fn doesnt_add_trailing_slash_to_feed() {
@@ -849,6 +1031,59 @@ skip_anchor_prefixes = [
         );
     }
 
+    #[test]
+    fn can_configure_content_lint_rules() {
+        let config_str = r#"
+title = "My site"
+base_url = "example.com"
+
+[content_lint]
+bare_url = "warn"
+missing_alt_text = "error"
+max_title_length = 60
+        "#;
+
+        let config = Config::parse(config_str).unwrap();
+        assert_eq!(config.content_lint.level(crate::LintRule::BareUrl), crate::LintLevel::Warn);
+        assert_eq!(
+            config.content_lint.level(crate::LintRule::MissingAltText),
+            crate::LintLevel::Error
+        );
+        // The rules are opt-in
+        assert_eq!(config.content_lint.level(crate::LintRule::MultipleH1), crate::LintLevel::Off);
+        assert_eq!(config.content_lint.max_title_length, 60);
+
+        let config_str = r#"
+title = "My site"
+base_url = "example.com"
+
+[content_lint]
+bare_urls = "off"
+        "#;
+        assert!(Config::parse(config_str).is_err());
+    }
+
+    #[test]
+    fn can_configure_spellcheck() {
+        let config = Config::parse("title = \"My site\"\nbase_url = \"example.com\"").unwrap();
+        assert_eq!(config.spellcheck.dictionaries, "dictionaries");
+        assert_eq!(config.spellcheck.level, crate::LinkCheckerLevel::Error);
+
+        let config_str = r#"
+title = "My site"
+base_url = "example.com"
+
+[spellcheck]
+dictionaries = "spelling"
+words = "words.txt"
+level = "warn"
+        "#;
+        let config = Config::parse(config_str).unwrap();
+        assert_eq!(config.spellcheck.dictionaries, "spelling");
+        assert_eq!(config.spellcheck.words.as_deref(), Some("words.txt"));
+        assert_eq!(config.spellcheck.level, crate::LinkCheckerLevel::Warn);
+    }
+
     #[test]
     fn link_checker_skip_prefixes() {
         let config_str = r#"
@@ -1092,5 +1327,113 @@ base_url = "example.com"
         assert!(config.generate_robots_txt);
     }
 
+    #[test]
+    fn can_parse_authors() {
+        let config = r#"
+base_url = "example.com"
+
+[authors.jane]
+name = "Jane Doe"
+bio = "Writes about *Rust*"
+extra = { mastodon = "https://example.com/@jane" }
+"#;
+        let config = Config::parse(config).unwrap();
+        let jane = &config.authors["jane"];
+        assert_eq!(jane.name, Some("Jane Doe".to_string()));
+        assert_eq!(jane.extra["mastodon"].as_str(), Some("https://example.com/@jane"));
+    }
+
+    #[test]
+    fn errors_on_taxonomy_using_authors_path_when_authors_are_set() {
+        let config = r#"
+base_url = "example.com"
+taxonomies = [{ name = "authors" }]
+
+[authors.jane]
+"#;
+        assert!(Config::parse(config).is_err());
+    }
+
+    #[test]
+    fn can_parse_archive() {
+        let config = r#"
+base_url = "example.com"
+
+[archive]
+section = "/blog/"
+paginate_by = 10
+"#;
+        let config = Config::parse(config).unwrap();
+        let archive = config.archive.unwrap();
+        assert_eq!(archive.section, "blog");
+        assert_eq!(archive.path, "archive");
+        assert!(archive.monthly);
+        assert_eq!(archive.paginate_by, Some(10));
+    }
+
+    #[test]
+    fn errors_on_empty_archive_path() {
+        let config = r#"
+base_url = "example.com"
+
+[archive]
+path = "/"
+"#;
+        assert!(Config::parse(config).is_err());
+    }
+
+    #[test]
+    fn errors_on_invalid_hyphenate_languages() {
+        let config = r#"
+base_url = "example.com"
+default_language = "fr"
+
+[languages.zz]
+
+[markdown]
+hyphenate = ["fr"]
+"#;
+        assert!(Config::parse(config).is_ok());
+        assert!(Config::parse(&config.replace(r#"["fr"]"#, r#"["de"]"#)).is_err());
+        assert!(Config::parse(&config.replace(r#"["fr"]"#, r#"["zz"]"#)).is_err());
+    }
+
+    #[test]
+    fn errors_on_tree_sitter_highlighter_without_the_feature() {
+        let config = r#"
+base_url = "example.com"
+
+[markdown]
+highlighter = "tree-sitter"
+"#;
+        let res = Config::parse(config);
+        if cfg!(feature = "tree-sitter") {
+            assert!(res.is_ok());
+        } else {
+            assert!(res.unwrap_err().to_string().contains("`markdown.highlighter`"));
+        }
+    }
+
+    #[test]
+    fn can_compile_code_callouts_pattern() {
+        let config = r#"
+base_url = "example.com"
+
+[markdown]
+code_callouts = '// \((\d+)\)'
+"#;
+        let config = Config::parse(config).unwrap();
+        let regex = config.markdown.code_callouts_regex.unwrap();
+        assert_eq!(&regex.captures("x(); // (3)").unwrap()[1], "3");
+
+        let without_group = r#"
+base_url = "example.com"
+
+[markdown]
+code_callouts = '// \(\d+\)'
+"#;
+        assert!(Config::parse(without_group).is_err());
+    }
+
     // TODO: add a test for excluding paginated pages
 }
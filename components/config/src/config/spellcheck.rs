@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::link_checker::LinkCheckerLevel;
+
+/// How `zola check --spelling` checks the spelling of the content
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Spellcheck {
+    /// The directory of the Hunspell dictionaries, `<lang>.aff` and `<lang>.dic`, relative to
+    /// the site
+    pub dictionaries: String,
+    /// A file of words accepted in every language, one per line, relative to the site
+    pub words: Option<String>,
+    /// Emit either "error" or "warn" for misspelled words
+    pub level: LinkCheckerLevel,
+}
+
+impl Default for Spellcheck {
+    fn default() -> Self {
+        Spellcheck {
+            dictionaries: "dictionaries".to_string(),
+            words: None,
+            level: LinkCheckerLevel::Error,
+        }
+    }
+}
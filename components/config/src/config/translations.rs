@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use errors::{anyhow, bail, Result};
+use libs::intl_pluralrules::{
+    operands::PluralOperands, PluralCategory, PluralRuleType, PluralRules,
+};
+use libs::once_cell::sync::Lazy;
+use libs::regex::{Captures, Regex};
+use libs::unic_langid::LanguageIdentifier;
+use serde::{Deserialize, Serialize};
+
+static PLACEHOLDER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+
+/// The forms of a message depending on a count, following the CLDR plural categories.
+/// Languages only use some of them, `other` being the only one required.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluralForms {
+    pub zero: Option<String>,
+    pub one: Option<String>,
+    pub two: Option<String>,
+    pub few: Option<String>,
+    pub many: Option<String>,
+    pub other: String,
+}
+
+impl PluralForms {
+    fn get(&self, category: PluralCategory) -> &str {
+        let form = match category {
+            PluralCategory::ZERO => &self.zero,
+            PluralCategory::ONE => &self.one,
+            PluralCategory::TWO => &self.two,
+            PluralCategory::FEW => &self.few,
+            PluralCategory::MANY => &self.many,
+            PluralCategory::OTHER => return &self.other,
+        };
+        form.as_deref().unwrap_or(&self.other)
+    }
+}
+
+/// A translated message, either a plain string or a table of plural forms
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Translation {
+    Text(String),
+    Plural(PluralForms),
+}
+
+impl From<&str> for Translation {
+    fn from(s: &str) -> Self {
+        Translation::Text(s.to_string())
+    }
+}
+
+/// Finds the plural category of `count` in the given language.
+/// Languages without plural rules always use the `other` category.
+fn plural_category(lang: &str, count: &str) -> Result<PluralCategory> {
+    let operands =
+        PluralOperands::try_from(count).map_err(|_| anyhow!("`{}` is not a valid count", count))?;
+    let rules = lang
+        .parse::<LanguageIdentifier>()
+        .ok()
+        .and_then(|id| PluralRules::create(id, PluralRuleType::CARDINAL).ok());
+
+    match rules {
+        Some(rules) => rules.select(operands).map_err(|e| anyhow!(e)),
+        None => Ok(PluralCategory::OTHER),
+    }
+}
+
+impl Translation {
+    /// Picks the message form matching the `count` argument, if any, and replaces the
+    /// `{name}` placeholders with the value of the matching argument.
+    /// Placeholders without a matching argument are left as is.
+    pub fn format(&self, lang: &str, args: &HashMap<String, String>) -> Result<String> {
+        let message = match self {
+            Translation::Text(s) => s.as_str(),
+            Translation::Plural(forms) => {
+                let count = match args.get("count") {
+                    Some(c) => c,
+                    None => bail!("A `count` argument is required for messages with plural forms"),
+                };
+                forms.get(plural_category(lang, count)?)
+            }
+        };
+
+        if args.is_empty() {
+            return Ok(message.to_string());
+        }
+
+        Ok(PLACEHOLDER_RE
+            .replace_all(message, |caps: &Captures| match args.get(&caps[1]) {
+                Some(val) => val.to_string(),
+                None => caps[0].to_string(),
+            })
+            .into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(count: &str) -> HashMap<String, String> {
+        let mut args = HashMap::new();
+        args.insert("count".to_string(), count.to_string());
+        args
+    }
+
+    #[test]
+    fn can_pick_plural_forms_per_language() {
+        let polish = Translation::Plural(PluralForms {
+            one: Some("{count} wpis".to_string()),
+            few: Some("{count} wpisy".to_string()),
+            many: Some("{count} wpisów".to_string()),
+            other: "{count} wpisu".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(polish.format("pl", &args("1")).unwrap(), "1 wpis");
+        assert_eq!(polish.format("pl", &args("3")).unwrap(), "3 wpisy");
+        assert_eq!(polish.format("pl", &args("5")).unwrap(), "5 wpisów");
+        assert_eq!(polish.format("pl", &args("22")).unwrap(), "22 wpisy");
+        assert_eq!(polish.format("pl", &args("1.5")).unwrap(), "1.5 wpisu");
+
+        let arabic = Translation::Plural(PluralForms {
+            zero: Some("لا مقالات".to_string()),
+            two: Some("مقالتان".to_string()),
+            other: "{count} مقالة".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(arabic.format("ar", &args("0")).unwrap(), "لا مقالات");
+        assert_eq!(arabic.format("ar", &args("2")).unwrap(), "مقالتان");
+        // `few` is missing so it falls back to `other`
+        assert_eq!(arabic.format("ar", &args("3")).unwrap(), "3 مقالة");
+    }
+
+    #[test]
+    fn uses_other_form_for_languages_without_rules() {
+        let forms = Translation::Plural(PluralForms {
+            one: Some("one".to_string()),
+            other: "other".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(forms.format("not-a-lang", &args("1")).unwrap(), "other");
+    }
+
+    #[test]
+    fn can_interpolate_arguments() {
+        let mut args = args("2");
+        args.insert("name".to_string(), "Zola".to_string());
+        let message = Translation::from("{name} has {count} {unknown}");
+        assert_eq!(message.format("en", &args).unwrap(), "Zola has 2 {unknown}");
+    }
+
+    #[test]
+    fn errors_on_plural_forms_without_count() {
+        let forms =
+            Translation::Plural(PluralForms { other: "x".to_string(), ..Default::default() });
+        assert!(forms.format("en", &HashMap::new()).is_err());
+        assert!(forms.format("en", &args("many")).is_err());
+    }
+}
@@ -8,6 +8,49 @@ use crate::config::Config;
 
 pub const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "z-" };
 
+/// The fence languages of terminal output with ANSI escape sequences
+pub const ANSI_LANGUAGES: [&str; 2] = ["ansi", "terminal"];
+
+/// The names and colours of the 16 basic ANSI colours, the bright ones last
+pub const ANSI_COLORS: [(&str, [u8; 3]); 16] = [
+    ("black", [0x00, 0x00, 0x00]),
+    ("red", [0xcd, 0x31, 0x31]),
+    ("green", [0x0d, 0xbc, 0x79]),
+    ("yellow", [0xe5, 0xe5, 0x10]),
+    ("blue", [0x24, 0x72, 0xc8]),
+    ("magenta", [0xbc, 0x3f, 0xbc]),
+    ("cyan", [0x11, 0xa8, 0xcd]),
+    ("white", [0xe5, 0xe5, 0xe5]),
+    ("bright-black", [0x66, 0x66, 0x66]),
+    ("bright-red", [0xf1, 0x4c, 0x4c]),
+    ("bright-green", [0x23, 0xd1, 0x8b]),
+    ("bright-yellow", [0xf5, 0xf5, 0x43]),
+    ("bright-blue", [0x3b, 0x8e, 0xea]),
+    ("bright-magenta", [0xd6, 0x70, 0xd6]),
+    ("bright-cyan", [0x29, 0xb8, 0xdb]),
+    ("bright-white", [0xff, 0xff, 0xff]),
+];
+
+/// The CSS of the classes used by the highlighting of `ansi` code blocks
+pub fn ansi_css() -> String {
+    let mut css = String::from("/* ANSI terminal output */\n");
+    for (name, [r, g, b]) in ANSI_COLORS {
+        css.push_str(&format!(".z-ansi-{name} {{ color: #{r:02x}{g:02x}{b:02x}; }}\n"));
+        css.push_str(&format!(
+            ".z-ansi-bg-{name} {{ background-color: #{r:02x}{g:02x}{b:02x}; }}\n"
+        ));
+    }
+    css.push_str(".z-ansi-bold { font-weight: bold; }\n");
+    css.push_str(".z-ansi-dim { opacity: 0.7; }\n");
+    css.push_str(".z-ansi-italic { font-style: italic; }\n");
+    css.push_str(".z-ansi-underline { text-decoration: underline; }\n");
+    css.push_str(".z-ansi-strikethrough { text-decoration: line-through; }\n");
+    css.push_str(
+        ".z-ansi-underline.z-ansi-strikethrough { text-decoration: underline line-through; }\n",
+    );
+    css
+}
+
 pub static SYNTAX_SET: Lazy<SyntaxSet> =
     Lazy::new(|| from_binary(include_bytes!("../sublime/syntaxes/newlines.packdump")));
 
@@ -24,6 +67,8 @@ pub enum HighlightSource {
     Plain,
     /// We didn't find the language in built-in and extra syntaxes
     NotFound,
+    /// Terminal output whose ANSI escape sequences are turned into styles
+    Ansi,
 }
 
 pub struct SyntaxAndTheme<'config> {
@@ -56,7 +101,14 @@ pub fn resolve_syntax_and_theme<'config>(
         // https://github.com/getzola/zola/issues/1211
         // https://github.com/getzola/zola/issues/1174
         let hacked_lang = if *lang == "js" || *lang == "javascript" { "ts" } else { lang };
-        if let Some(syntax) = SYNTAX_SET.find_syntax_by_token(hacked_lang) {
+        if ANSI_LANGUAGES.contains(lang) {
+            SyntaxAndTheme {
+                syntax: SYNTAX_SET.find_syntax_plain_text(),
+                syntax_set: &SYNTAX_SET as &SyntaxSet,
+                theme,
+                source: HighlightSource::Ansi,
+            }
+        } else if let Some(syntax) = SYNTAX_SET.find_syntax_by_token(hacked_lang) {
             SyntaxAndTheme {
                 syntax,
                 syntax_set: &SYNTAX_SET as &SyntaxSet,
@@ -5,13 +5,21 @@ mod theme;
 use std::path::Path;
 
 pub use crate::config::{
+    archive::ArchiveConfig,
+    authors::AuthorConfig,
+    content_lint::{ContentLint, LintLevel, LintRule},
+    glossary::{Glossary, GlossaryTerm},
+    i18n_check::I18nCheck,
     languages::LanguageOptions,
     link_checker::LinkChecker,
     link_checker::LinkCheckerLevel,
+    markup::{Highlighter, Markdown},
     search::{IndexFormat, Search},
     slugify::Slugify,
+    spellcheck::Spellcheck,
     taxonomies::TaxonomyConfig,
-    Config,
+    translations::{PluralForms, Translation},
+    Config, AUTHORS_PATH,
 };
 use errors::Result;
 
@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use config::{ArchiveConfig, Config};
+use errors::{Context as ErrorContext, Result};
+use libs::tera::{Context, Tera};
+use utils::templates::render_template;
+
+use crate::library::Library;
+use crate::ser::SerializingPage;
+use crate::sorting::sort_pages;
+use crate::{Page, SortBy};
+
+/// A link to another archive, used to navigate between them in templates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArchiveLink<'a> {
+    year: i32,
+    month: Option<u8>,
+    permalink: &'a str,
+    page_count: usize,
+}
+
+impl<'a> ArchiveLink<'a> {
+    fn new(archive: &'a Archive) -> Self {
+        ArchiveLink {
+            year: archive.year,
+            month: archive.month,
+            permalink: &archive.permalink,
+            page_count: archive.pages.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SerializedArchive<'a> {
+    year: i32,
+    month: Option<u8>,
+    lang: &'a str,
+    path: &'a str,
+    permalink: &'a str,
+    pages: Vec<SerializingPage<'a>>,
+    page_count: usize,
+    /// The monthly archives of a yearly archive
+    months: Vec<ArchiveLink<'a>>,
+    /// All the yearly archives in that language, most recent first
+    years: Vec<ArchiveLink<'a>>,
+}
+
+impl<'a> SerializedArchive<'a> {
+    pub fn from_archive(archive: &'a Archive, library: &'a Library, include_pages: bool) -> Self {
+        let mut pages = vec![];
+
+        if include_pages {
+            for p in &archive.pages {
+                pages.push(SerializingPage::new(&library.pages[p], Some(library), false));
+            }
+        }
+
+        let same_lang = library.archives.iter().filter(|a| a.lang == archive.lang);
+        let (yearly, monthly): (Vec<_>, Vec<_>) = same_lang.partition(|a| a.month.is_none());
+        let months = if archive.month.is_none() {
+            monthly.into_iter().filter(|a| a.year == archive.year).map(ArchiveLink::new).collect()
+        } else {
+            Vec::new()
+        };
+
+        SerializedArchive {
+            year: archive.year,
+            month: archive.month,
+            lang: &archive.lang,
+            path: &archive.path,
+            permalink: &archive.permalink,
+            pages,
+            page_count: archive.pages.len(),
+            months,
+            years: yearly.into_iter().map(ArchiveLink::new).collect(),
+        }
+    }
+}
+
+/// The pages of a section published in a given year, or a given month of a year
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Archive {
+    pub lang: String,
+    pub year: i32,
+    /// Only set for monthly archives
+    pub month: Option<u8>,
+    pub path: String,
+    pub permalink: String,
+    /// The `_index.md` file of the archived section
+    pub section: PathBuf,
+    /// Sorted by date, most recent first
+    pub pages: Vec<PathBuf>,
+}
+
+impl Archive {
+    fn new(
+        year: i32,
+        month: Option<u8>,
+        section: &Path,
+        lang: &str,
+        archive_pages: &[&Page],
+        config: &Config,
+        archive_config: &ArchiveConfig,
+    ) -> Self {
+        let mut path = if lang != config.default_language {
+            format!("/{}/{}/{}/", lang, archive_config.path, year)
+        } else {
+            format!("/{}/{}/", archive_config.path, year)
+        };
+        if let Some(m) = month {
+            path = format!("{}{:02}/", path, m);
+        }
+        let permalink = config.make_permalink(&path);
+        let (pages, _) = sort_pages(archive_pages, SortBy::Date);
+
+        Archive {
+            lang: lang.to_string(),
+            year,
+            month,
+            path,
+            permalink,
+            section: section.to_path_buf(),
+            pages,
+        }
+    }
+
+    /// Groups the pages of a section by year and by month, if enabled.
+    /// Pages without a date are not part of any archive.
+    /// Archives are sorted from the most recent to the oldest, each year coming before its months.
+    pub fn find_all(
+        section: &Path,
+        lang: &str,
+        pages: &[&Page],
+        config: &Config,
+        archive_config: &ArchiveConfig,
+    ) -> Vec<Archive> {
+        let mut by_year: BTreeMap<i32, BTreeMap<u8, Vec<&Page>>> = BTreeMap::new();
+        for page in pages {
+            if let Some((year, month, _)) = page.meta.datetime_tuple {
+                by_year.entry(year).or_default().entry(month).or_default().push(page);
+            }
+        }
+
+        let mut archives = Vec::new();
+        for (year, months) in by_year.into_iter().rev() {
+            let year_pages: Vec<_> = months.values().flatten().copied().collect();
+            archives.push(Archive::new(
+                year,
+                None,
+                section,
+                lang,
+                &year_pages,
+                config,
+                archive_config,
+            ));
+            if archive_config.monthly {
+                for (month, month_pages) in months.into_iter().rev() {
+                    archives.push(Archive::new(
+                        year,
+                        Some(month),
+                        section,
+                        lang,
+                        &month_pages,
+                        config,
+                        archive_config,
+                    ));
+                }
+            }
+        }
+        archives
+    }
+
+    pub fn serialize<'a>(&'a self, library: &'a Library) -> SerializedArchive<'a> {
+        SerializedArchive::from_archive(self, library, true)
+    }
+
+    pub fn serialize_without_pages<'a>(&'a self, library: &'a Library) -> SerializedArchive<'a> {
+        SerializedArchive::from_archive(self, library, false)
+    }
+
+    pub fn render(&self, tera: &Tera, config: &Config, library: &Library) -> Result<String> {
+        let mut context = Context::new();
+        context.insert("config", &config.serialize(&self.lang));
+        context.insert("lang", &self.lang);
+        context.insert("archive", &self.serialize(library));
+        context.insert("section", &library.sections[&self.section].serialize_basic(library));
+        context.insert("current_url", &self.permalink);
+        context.insert("current_path", &self.path);
+
+        render_template("archive.html", tera, context, &config.theme)
+            .with_context(|| format!("Failed to render the archive of `{}`", self.path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_page(path: &str, date: &str) -> Page {
+        let mut page = Page::default();
+        page.file.path = PathBuf::from(path);
+        page.meta.date = Some(date.to_owned());
+        page.meta.date_to_datetime();
+        page
+    }
+
+    #[test]
+    fn can_group_pages_by_year_and_month() {
+        let config = Config::default_for_test();
+        let archive_config = ArchiveConfig::default();
+        let pages = [
+            create_page("a.md", "2023-12-24"),
+            create_page("b.md", "2024-05-01"),
+            create_page("c.md", "2024-05-20"),
+            create_page("d.md", "2024-01-10"),
+            Page::default(),
+        ];
+        let pages: Vec<_> = pages.iter().collect();
+        let archives = Archive::find_all(
+            Path::new("content/_index.md"),
+            "en",
+            &pages,
+            &config,
+            &archive_config,
+        );
+
+        let paths: Vec<_> = archives.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/archive/2024/",
+                "/archive/2024/05/",
+                "/archive/2024/01/",
+                "/archive/2023/",
+                "/archive/2023/12/"
+            ]
+        );
+        assert_eq!(
+            archives[0].pages,
+            vec![PathBuf::from("c.md"), PathBuf::from("b.md"), PathBuf::from("d.md")]
+        );
+        assert_eq!(archives[1].permalink, "http://a-website.com/archive/2024/05/");
+    }
+
+    #[test]
+    fn can_skip_monthly_archives_in_other_language() {
+        let config = Config::default_for_test();
+        let archive_config =
+            ArchiveConfig { monthly: false, path: "posts".to_string(), ..Default::default() };
+        let pages = [create_page("a.fr.md", "2024-05-01")];
+        let pages: Vec<_> = pages.iter().collect();
+        let archives = Archive::find_all(
+            Path::new("content/_index.fr.md"),
+            "fr",
+            &pages,
+            &config,
+            &archive_config,
+        );
+
+        assert_eq!(archives.len(), 1);
+        assert_eq!(archives[0].path, "/fr/posts/2024/");
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use config::{AuthorConfig, Config, AUTHORS_PATH};
+use errors::{Context as ErrorContext, Result};
+use libs::tera::{Context, Tera};
+use libs::toml::Value as Toml;
+use utils::slugs::slugify_paths;
+use utils::templates::render_template;
+
+use crate::library::Library;
+use crate::ser::SerializingPage;
+use crate::sorting::sort_pages;
+use crate::{Page, SortBy};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SerializedAuthor<'a> {
+    id: &'a str,
+    name: &'a str,
+    email: &'a Option<String>,
+    bio: &'a Option<String>,
+    avatar: &'a Option<String>,
+    extra: &'a HashMap<String, Toml>,
+    lang: &'a str,
+    path: &'a str,
+    permalink: &'a str,
+    pages: Vec<SerializingPage<'a>>,
+    page_count: usize,
+}
+
+// Only there so the pages can keep deriving `Eq`: floats in `extra` are never compared
+impl Eq for SerializedAuthor<'_> {}
+
+impl<'a> SerializedAuthor<'a> {
+    pub fn from_author(author: &'a Author, library: &'a Library, include_pages: bool) -> Self {
+        let mut pages = vec![];
+
+        if include_pages {
+            for p in &author.pages {
+                pages.push(SerializingPage::new(&library.pages[p], Some(library), false));
+            }
+        }
+
+        SerializedAuthor {
+            id: &author.id,
+            name: author.config.name.as_deref().unwrap_or(&author.id),
+            email: &author.config.email,
+            bio: &author.config.bio,
+            avatar: &author.config.avatar,
+            extra: &author.config.extra,
+            lang: &author.lang,
+            path: &author.path,
+            permalink: &author.permalink,
+            pages,
+            page_count: author.pages.len(),
+        }
+    }
+}
+
+/// An author from the `[authors]` config with all their pages in a given language
+#[derive(Debug, Clone, PartialEq)]
+pub struct Author {
+    /// The key of the author in `[authors]`, used in the pages front matter
+    pub id: String,
+    pub lang: String,
+    pub slug: String,
+    pub path: String,
+    pub permalink: String,
+    pub config: AuthorConfig,
+    /// Sorted by date, pages without dates being at the end
+    pub pages: Vec<PathBuf>,
+}
+
+impl Author {
+    pub fn new(id: &str, lang: &str, author_pages: &[&Page], config: &Config) -> Self {
+        let slug = slugify_paths(id, config.slugify.paths);
+        let path = if lang != config.default_language {
+            format!("/{}/{}/{}/", lang, AUTHORS_PATH, slug)
+        } else {
+            format!("/{}/{}/", AUTHORS_PATH, slug)
+        };
+        let permalink = config.make_permalink(&path);
+
+        let (mut pages, ignored_pages) = sort_pages(author_pages, SortBy::Date);
+        pages.extend(ignored_pages);
+
+        Author {
+            id: id.to_string(),
+            lang: lang.to_string(),
+            slug,
+            path,
+            permalink,
+            config: config.authors[id].clone(),
+            pages,
+        }
+    }
+
+    /// Author profiles are always rendered in the default language but only
+    /// when they have some content in the other ones
+    pub fn should_render(&self, config: &Config) -> bool {
+        self.lang == config.default_language || !self.pages.is_empty()
+    }
+
+    pub fn serialize<'a>(&'a self, library: &'a Library) -> SerializedAuthor<'a> {
+        SerializedAuthor::from_author(self, library, true)
+    }
+
+    pub fn serialize_without_pages<'a>(&'a self, library: &'a Library) -> SerializedAuthor<'a> {
+        SerializedAuthor::from_author(self, library, false)
+    }
+
+    pub fn render(&self, tera: &Tera, config: &Config, library: &Library) -> Result<String> {
+        let mut context = Context::new();
+        context.insert("config", &config.serialize(&self.lang));
+        context.insert("lang", &self.lang);
+        context.insert("author", &self.serialize(library));
+        context.insert("current_url", &self.permalink);
+        context.insert("current_path", &self.path);
+
+        render_template("author.html", tera, context, &config.theme)
+            .with_context(|| format!("Failed to render the profile of author `{}`", self.id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{AuthorConfig, Config};
+
+    use super::*;
+
+    #[test]
+    fn can_create_author_in_other_language() {
+        let mut config = Config::default_for_test();
+        config.authors.insert("Jane".to_string(), AuthorConfig::default());
+        let author = Author::new("Jane", "fr", &[], &config);
+
+        assert_eq!(author.slug, "jane");
+        assert_eq!(author.path, "/fr/authors/jane/");
+        assert_eq!(author.permalink, "http://a-website.com/fr/authors/jane/");
+        assert!(!author.should_render(&config));
+        let library = Library::default();
+        assert_eq!(author.serialize(&library).name, "Jane");
+    }
+}
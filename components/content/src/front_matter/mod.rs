@@ -1,7 +1,9 @@
 mod page;
+mod schema;
 mod section;
 mod split;
 
-pub use page::PageFrontMatter;
+pub use page::{parse_datetime, PageFrontMatter};
+pub use schema::{FieldSchema, FieldType, PageSchema};
 pub use section::SectionFrontMatter;
 pub use split::{split_page_content, split_section_content};
@@ -6,6 +6,7 @@ use time::format_description::well_known::Rfc3339;
 use time::macros::{format_description, time};
 use time::{Date, OffsetDateTime, PrimitiveDateTime};
 
+use config::LintRule;
 use errors::{bail, Result};
 use utils::de::{fix_toml_dates, from_unknown_datetime};
 
@@ -39,6 +40,12 @@ pub struct PageFrontMatter {
     pub datetime_tuple: Option<(i32, u8, u8)>,
     /// Whether this page is a draft
     pub draft: bool,
+    /// Whether this page is rendered but left out of its sections, taxonomies, feeds,
+    /// sitemap and search indexes
+    pub unlisted: bool,
+    /// Links the page to its translations when their file names differ
+    #[serde(skip_serializing)]
+    pub translation_key: Option<String>,
     /// Prevent generation of a folder for current page
     /// Defaults to `true`
     #[serde(skip_serializing)]
@@ -66,6 +73,15 @@ pub struct PageFrontMatter {
     /// Defaults to `true` but is only used if search if explicitly enabled in the config.
     #[serde(skip_serializing)]
     pub in_search_index: bool,
+    /// Whether the glossary terms are linked in the content of the page. Defaults to `true`
+    #[serde(skip_serializing)]
+    pub glossary: bool,
+    /// The content lint rules of `zola check` to skip for this page
+    #[serde(skip_serializing)]
+    pub lint_ignore: Vec<LintRule>,
+    /// The words accepted by `zola check --spelling` in this page
+    #[serde(skip_serializing)]
+    pub spelling_ignore: Vec<String>,
     /// Any extra parameter present in the front matter
     pub extra: Map<String, Value>,
 }
@@ -76,7 +92,7 @@ pub struct PageFrontMatter {
 /// 2. a local datetime (RFC3339 with timezone omitted)
 /// 3. a local date (YYYY-MM-DD).
 /// This tries each in order.
-fn parse_datetime(d: &str) -> Option<OffsetDateTime> {
+pub fn parse_datetime(d: &str) -> Option<OffsetDateTime> {
     OffsetDateTime::parse(d, &Rfc3339)
         .or_else(|_| OffsetDateTime::parse(format!("{}Z", d).as_ref(), &Rfc3339))
         .or_else(|_| match Date::parse(d, &format_description!("[year]-[month]-[day]")) {
@@ -146,6 +162,9 @@ impl Default for PageFrontMatter {
     fn default() -> PageFrontMatter {
         PageFrontMatter {
             in_search_index: true,
+            glossary: true,
+            lint_ignore: Vec::new(),
+            spelling_ignore: Vec::new(),
             title: None,
             description: None,
             updated: None,
@@ -155,6 +174,8 @@ impl Default for PageFrontMatter {
             datetime: None,
             datetime_tuple: None,
             draft: false,
+            unlisted: false,
+            translation_key: None,
             render: true,
             slug: None,
             path: None,
@@ -172,6 +193,7 @@ impl Default for PageFrontMatter {
 mod tests {
     use crate::front_matter::page::PageFrontMatter;
     use crate::front_matter::split::RawFrontMatter;
+    use config::LintRule;
     use libs::tera::to_value;
     use test_case::test_case;
     use time::macros::datetime;
@@ -200,6 +222,19 @@ description: hey there
         assert_eq!(res.description.unwrap(), "hey there".to_string())
     }
 
+    #[test_case(&RawFrontMatter::Toml(r#"
+lint_ignore = ["bare_url", "multiple_h1"]
+"#); "toml")]
+    #[test_case(&RawFrontMatter::Yaml(r#"
+lint_ignore: [bare_url, multiple_h1]
+"#); "yaml")]
+    fn can_parse_lint_ignore(content: &RawFrontMatter) {
+        let res = PageFrontMatter::parse(content).unwrap();
+        assert_eq!(res.lint_ignore, vec![LintRule::BareUrl, LintRule::MultipleH1]);
+        let invalid = RawFrontMatter::Toml(r#"lint_ignore = ["bare_urls"]"#);
+        assert!(PageFrontMatter::parse(&invalid).is_err());
+    }
+
     #[test_case(&RawFrontMatter::Toml(r#"title = |\n"#); "toml")]
     #[test_case(&RawFrontMatter::Yaml(r#"title: |\n"#); "yaml")]
     fn errors_with_invalid_front_matter(content: &RawFrontMatter) {
@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+
+use libs::tera::{to_value, Map, Value};
+use serde::{Deserialize, Serialize};
+
+use errors::{bail, Result};
+use utils::de::from_unknown_datetime;
+
+use crate::front_matter::page::{parse_datetime, PageFrontMatter};
+
+/// The top-level page front matter keys that can be described in a schema.
+/// Nested keys are written with dots, eg `extra.hero_image` or `taxonomies.tags`.
+const SCHEMA_TOP_LEVEL_KEYS: &[&str] = &[
+    "title",
+    "description",
+    "date",
+    "updated",
+    "draft",
+    "slug",
+    "path",
+    "weight",
+    "authors",
+    "aliases",
+    "template",
+    "taxonomies",
+    "extra",
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Integer,
+    /// Integers are accepted as well
+    Float,
+    Boolean,
+    /// A datetime or a string that can be parsed as one
+    Date,
+    Array,
+    Table,
+}
+
+impl FieldType {
+    fn name(&self) -> &'static str {
+        match self {
+            FieldType::String => "string",
+            FieldType::Integer => "integer",
+            FieldType::Float => "float",
+            FieldType::Boolean => "boolean",
+            FieldType::Date => "date",
+            FieldType::Array => "array",
+            FieldType::Table => "table",
+        }
+    }
+
+    fn matches(&self, value: &Value) -> bool {
+        match self {
+            FieldType::String => value.is_string(),
+            FieldType::Integer => value.is_i64() || value.is_u64(),
+            FieldType::Float => value.is_number(),
+            FieldType::Boolean => value.is_boolean(),
+            FieldType::Date => value.as_str().and_then(parse_datetime).is_some(),
+            FieldType::Array => value.is_array(),
+            FieldType::Table => value.is_object(),
+        }
+    }
+}
+
+/// The constraints on a single front matter field
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FieldSchema {
+    /// Whether the field has to be set. Defaults to `false`
+    pub required: bool,
+    /// The expected type of the value. Not checked if not set
+    #[serde(rename = "type")]
+    pub kind: Option<FieldType>,
+    /// The only values allowed for that field. For arrays, it applies to every item.
+    #[serde(rename = "enum")]
+    pub allowed: Vec<Value>,
+    /// The earliest date allowed for that field
+    #[serde(deserialize_with = "from_unknown_datetime")]
+    pub min: Option<String>,
+    /// The latest date allowed for that field
+    #[serde(deserialize_with = "from_unknown_datetime")]
+    pub max: Option<String>,
+}
+
+impl FieldSchema {
+    fn validate(&self, key: &str, value: Option<&Value>, errors: &mut Vec<String>) {
+        let value = match value {
+            Some(v) => v,
+            None => {
+                if self.required {
+                    errors.push(format!("`{}` is required", key));
+                }
+                return;
+            }
+        };
+
+        if let Some(kind) = self.kind {
+            if !kind.matches(value) {
+                errors.push(format!("`{}` should be a {} but got {}", key, kind.name(), value));
+                return;
+            }
+        }
+
+        if !self.allowed.is_empty() {
+            let items = match value {
+                Value::Array(items) => items.iter().collect(),
+                v => vec![v],
+            };
+            for item in items {
+                if !self.allowed.contains(item) {
+                    errors.push(format!(
+                        "`{}` should be one of {} but got {}",
+                        key,
+                        Value::Array(self.allowed.clone()),
+                        item
+                    ));
+                }
+            }
+        }
+
+        if self.min.is_none() && self.max.is_none() {
+            return;
+        }
+
+        let datetime = match value.as_str().and_then(parse_datetime) {
+            Some(d) => d,
+            None => {
+                errors.push(format!("`{}` should be a date but got {}", key, value));
+                return;
+            }
+        };
+        if let Some(ref min) = self.min {
+            if parse_datetime(min).is_some_and(|m| datetime < m) {
+                errors.push(format!("`{}` should not be before {} but got {}", key, min, value));
+            }
+        }
+        if let Some(ref max) = self.max {
+            if parse_datetime(max).is_some_and(|m| datetime > m) {
+                errors.push(format!("`{}` should not be after {} but got {}", key, max, value));
+            }
+        }
+    }
+}
+
+/// A schema for the front matter of pages, declared by a section in `page_schema`.
+/// Keys are the (dotted) path to the field, eg `title` or `extra.product_id`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PageSchema {
+    pub fields: BTreeMap<String, FieldSchema>,
+}
+
+impl PageSchema {
+    /// Ensures all keys refer to something that can exist in a page front matter
+    pub fn validate_keys(&self) -> Result<()> {
+        for key in self.fields.keys() {
+            let top_level = key.split('.').next().unwrap();
+            if !SCHEMA_TOP_LEVEL_KEYS.contains(&top_level) {
+                bail!("`page_schema` contains unknown page front matter field `{}`", key);
+            }
+            if (top_level == "extra" || top_level == "taxonomies") && key == top_level {
+                bail!("`page_schema` field `{}` needs to be a nested key like `{}.name`", key, key);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a list of all the violations of the schema for that page front matter
+    pub fn validate(&self, meta: &PageFrontMatter) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (key, field) in &self.fields {
+            field.validate(key, find_field(meta, key).as_ref(), &mut errors);
+        }
+        errors
+    }
+}
+
+fn find_in_map(map: &Map<String, Value>, path: &str) -> Option<Value> {
+    let mut parts = path.split('.');
+    let mut current = map.get(parts.next()?)?;
+    for part in parts {
+        current = current.as_object()?.get(part)?;
+    }
+    Some(current.clone())
+}
+
+/// Finds the value of a dotted key in the page front matter.
+/// Returns `None` if the field is not set.
+fn find_field(meta: &PageFrontMatter, key: &str) -> Option<Value> {
+    let (top_level, rest) = match key.split_once('.') {
+        Some((t, r)) => (t, Some(r)),
+        None => (key, None),
+    };
+
+    match (top_level, rest) {
+        ("extra", Some(rest)) => find_in_map(&meta.extra, rest),
+        ("taxonomies", Some(name)) => meta.taxonomies.get(name).map(|t| to_value(t).unwrap()),
+        ("title", None) => meta.title.as_ref().map(|v| to_value(v).unwrap()),
+        ("description", None) => meta.description.as_ref().map(|v| to_value(v).unwrap()),
+        ("date", None) => meta.date.as_ref().map(|v| to_value(v).unwrap()),
+        ("updated", None) => meta.updated.as_ref().map(|v| to_value(v).unwrap()),
+        ("draft", None) => Some(Value::Bool(meta.draft)),
+        ("slug", None) => meta.slug.as_ref().map(|v| to_value(v).unwrap()),
+        ("path", None) => meta.path.as_ref().map(|v| to_value(v).unwrap()),
+        ("weight", None) => meta.weight.map(|v| to_value(v).unwrap()),
+        ("template", None) => meta.template.as_ref().map(|v| to_value(v).unwrap()),
+        ("authors", None) if !meta.authors.is_empty() => Some(to_value(&meta.authors).unwrap()),
+        ("aliases", None) if !meta.aliases.is_empty() => Some(to_value(&meta.aliases).unwrap()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::front_matter::split::RawFrontMatter;
+
+    fn schema(toml: &str) -> PageSchema {
+        libs::toml::from_str(toml).unwrap()
+    }
+
+    fn page(toml: &str) -> PageFrontMatter {
+        PageFrontMatter::parse(&RawFrontMatter::Toml(toml)).unwrap()
+    }
+
+    #[test]
+    fn can_report_missing_required_fields() {
+        let schema = schema(
+            r#"
+"extra.hero_image" = { required = true }
+"extra.product_id" = { required = true, type = "string" }
+date = { required = false }
+"#,
+        );
+        let errors = schema.validate(&page("extra.product_id = \"abc\""));
+        assert_eq!(errors, vec!["`extra.hero_image` is required".to_string()]);
+    }
+
+    #[test]
+    fn can_check_types() {
+        let schema = schema(
+            r#"
+weight = { type = "integer" }
+"extra.price" = { type = "float" }
+"extra.stock" = { type = "integer" }
+"extra.released" = { type = "date" }
+"#,
+        );
+        assert!(schema
+            .validate(&page("weight = 2\n[extra]\nprice = 3\nstock = 4\nreleased = 2024-01-01"))
+            .is_empty());
+
+        let errors = schema.validate(&page("[extra]\nprice = \"3\"\nstock = 4.5\nreleased = 1"));
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0], "`extra.price` should be a float but got \"3\"");
+    }
+
+    #[test]
+    fn can_check_allowed_values() {
+        let schema = schema(
+            r#"
+"extra.category" = { enum = ["hardware", "software"] }
+"taxonomies.tags" = { enum = ["rust", "python"] }
+"#,
+        );
+        let errors = schema.validate(&page(
+            "taxonomies = { tags = [\"rust\", \"go\"] }\n[extra]\ncategory = \"food\"",
+        ));
+        assert_eq!(
+            errors,
+            vec![
+                "`extra.category` should be one of [\"hardware\",\"software\"] but got \"food\""
+                    .to_string(),
+                "`taxonomies.tags` should be one of [\"rust\",\"python\"] but got \"go\""
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_check_date_ranges() {
+        let schema = schema(r#"date = { min = 2020-01-01, max = "2020-12-31" }"#);
+        assert!(schema.validate(&page("date = 2020-05-05")).is_empty());
+        assert!(schema.validate(&page("")).is_empty());
+        let errors = schema.validate(&page("date = 2019-05-05"));
+        assert_eq!(errors, vec!["`date` should not be before 2020-01-01 but got \"2019-05-05\""]);
+        let errors = schema.validate(&page("date = 2021-05-05"));
+        assert_eq!(errors, vec!["`date` should not be after 2020-12-31 but got \"2021-05-05\""]);
+    }
+
+    #[test]
+    fn errors_on_unknown_keys() {
+        assert!(schema(r#""extra.a.b" = {}"#).validate_keys().is_ok());
+        assert!(schema(r#"tittle = {}"#).validate_keys().is_err());
+        assert!(schema(r#"extra = {}"#).validate_keys().is_err());
+    }
+}
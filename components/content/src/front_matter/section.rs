@@ -1,10 +1,12 @@
 use libs::tera::{Map, Value};
 use serde::{Deserialize, Serialize};
 
+use config::LintRule;
 use errors::Result;
 use utils::de::fix_toml_dates;
-use utils::types::InsertAnchor;
+use utils::types::{AutoSummary, InsertAnchor};
 
+use crate::front_matter::schema::PageSchema;
 use crate::front_matter::split::RawFrontMatter;
 use crate::SortBy;
 
@@ -27,6 +29,9 @@ pub struct SectionFrontMatter {
     pub weight: usize,
     /// whether the section is a draft
     pub draft: bool,
+    /// Links the section to its translations when their file names differ
+    #[serde(skip_serializing)]
+    pub translation_key: Option<String>,
     /// Optional template, if we want to specify which template to render for that section
     #[serde(skip_serializing)]
     pub template: Option<String>,
@@ -42,6 +47,10 @@ pub struct SectionFrontMatter {
     /// Whether to insert a link for each header like the ones you can see in this site if you hover one
     /// The default template can be overridden by creating a `anchor-link.html` in the `templates` directory
     pub insert_anchor_links: Option<InsertAnchor>,
+    /// How to generate the summary of the pages of that section without a `<!-- more -->` marker.
+    /// Overrides `auto_summary` from the `[markdown]` config if set
+    #[serde(skip_serializing)]
+    pub auto_summary: Option<AutoSummary>,
     /// Whether to render that section or not. Defaults to `true`.
     /// Useful when the section is only there to organize things but is not meant
     /// to be used directly, like a posts section in a personal site
@@ -56,6 +65,15 @@ pub struct SectionFrontMatter {
     /// Defaults to `true` but is only used if search if explicitly enabled in the config.
     #[serde(skip_serializing)]
     pub in_search_index: bool,
+    /// Whether the glossary terms are linked in the content of the section. Defaults to `true`
+    #[serde(skip_serializing)]
+    pub glossary: bool,
+    /// The content lint rules of `zola check` to skip for this section
+    #[serde(skip_serializing)]
+    pub lint_ignore: Vec<LintRule>,
+    /// The words accepted by `zola check --spelling` in this section
+    #[serde(skip_serializing)]
+    pub spelling_ignore: Vec<String>,
     /// Whether the section should pass its pages on to the parent section. Defaults to `false`.
     /// Useful when the section shouldn't split up the parent section, like
     /// sections for each year under a posts section.
@@ -63,6 +81,14 @@ pub struct SectionFrontMatter {
     /// Optional template for all pages in this section (including the pages of children section)
     #[serde(skip_serializing)]
     pub page_template: Option<String>,
+    /// Optional schema the front matter of all pages in this section (including the pages of
+    /// children section) will be validated against
+    #[serde(skip_serializing)]
+    pub page_schema: Option<PageSchema>,
+    /// Optional permalink pattern, eg `/:year/:month/:slug/`, for all pages in this section
+    /// (including the pages of children section)
+    #[serde(skip_serializing)]
+    pub page_permalink: Option<String>,
     /// All aliases for that page. Zola will create HTML templates that will
     /// redirect to this
     #[serde(skip_serializing)]
@@ -83,6 +109,10 @@ impl SectionFrontMatter {
             _ => unreachable!("Got something other than a table in section extra"),
         };
 
+        if let Some(ref schema) = f.page_schema {
+            schema.validate_keys()?;
+        }
+
         Ok(f)
     }
 
@@ -109,13 +139,20 @@ impl Default for SectionFrontMatter {
             render: true,
             redirect_to: None,
             insert_anchor_links: None,
+            auto_summary: None,
             in_search_index: true,
+            glossary: true,
+            lint_ignore: Vec::new(),
+            spelling_ignore: Vec::new(),
             transparent: false,
             page_template: None,
+            page_schema: None,
+            page_permalink: None,
             aliases: Vec::new(),
             generate_feeds: false,
             extra: Map::new(),
             draft: false,
+            translation_key: None,
         }
     }
 }
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+use serde::Serialize;
+
+use errors::{bail, Context, Result};
+
+// Separators unlikely to appear in a commit author name
+const COMMIT_START: char = '\u{1e}';
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GitCommit {
+    pub hash: String,
+    /// The author date, as a RFC3339 datetime
+    pub date: String,
+    pub author: String,
+}
+
+/// What the local git history knows about a content file
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GitInfo {
+    /// The most recent commit touching that file
+    pub last_commit: GitCommit,
+    /// The commit that added that file
+    pub first_commit: GitCommit,
+    /// The authors of all the commits touching that file, most recent first and without duplicates
+    pub authors: Vec<String>,
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| "Failed to run `git`, is it installed?")?;
+    if !output.status.success() {
+        bail!(
+            "`git {}` failed in {}: {}",
+            args.join(" "),
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Reads the history of the git repository containing `content_path` in a single `git log` call.
+/// The keys of the map are the path of the files relative to `content_path`, the same as `FileInfo::relative`.
+/// Only the local repository is used: uncommitted files are simply missing from the map.
+pub fn read_git_history(content_path: &Path) -> Result<HashMap<String, GitInfo>> {
+    // The path of `content_path` relative to the root of the repository, with a trailing slash
+    let prefix = run_git(content_path, &["rev-parse", "--show-prefix"])
+        .context("`use_git_history` is enabled but the site is not in a git repository")?;
+    let log = run_git(
+        content_path,
+        &[
+            "-c",
+            "core.quotePath=false",
+            "log",
+            "--no-merges",
+            "--no-renames",
+            "--format=%x1e%H%x1f%aI%x1f%an",
+            "--name-only",
+            "--",
+            ".",
+        ],
+    )?;
+    Ok(parse_git_log(&log, prefix.trim()))
+}
+
+/// Parses the output of `git log --name-only` with the format used in `read_git_history`.
+/// Commits are listed from the most recent to the oldest.
+fn parse_git_log(log: &str, prefix: &str) -> HashMap<String, GitInfo> {
+    let mut history: HashMap<String, GitInfo> = HashMap::new();
+
+    for entry in log.split(COMMIT_START).filter(|e| !e.trim().is_empty()) {
+        let mut lines = entry.lines();
+        let mut fields = lines.next().unwrap_or_default().splitn(3, FIELD_SEPARATOR);
+        let commit = match (fields.next(), fields.next(), fields.next()) {
+            (Some(hash), Some(date), Some(author)) => GitCommit {
+                hash: hash.to_string(),
+                date: date.to_string(),
+                author: author.to_string(),
+            },
+            _ => continue,
+        };
+
+        for file in lines.filter(|l| !l.is_empty()) {
+            let relative = file.strip_prefix(prefix).unwrap_or(file);
+            let info = history
+                .entry(relative.to_string())
+                .or_insert_with(|| GitInfo { last_commit: commit.clone(), ..Default::default() });
+            info.first_commit = commit.clone();
+            if !info.authors.contains(&commit.author) {
+                info.authors.push(commit.author.clone());
+            }
+        }
+    }
+
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_git_log() {
+        let log = "\u{1e}ccc\u{1f}2024-03-01T10:00:00+01:00\u{1f}Bob\n\ncontent/blog/hello.md\n\
+            \u{1e}bbb\u{1f}2024-02-01T10:00:00+01:00\u{1f}Alice Doe\n\ncontent/blog/hello.md\ncontent/about.md\n\
+            \u{1e}aaa\u{1f}2024-01-01T10:00:00Z\u{1f}Bob\n\ncontent/blog/hello.md\n";
+        let history = parse_git_log(log, "content/");
+
+        assert_eq!(history.len(), 2);
+        let hello = &history["blog/hello.md"];
+        assert_eq!(hello.last_commit.hash, "ccc");
+        assert_eq!(hello.last_commit.date, "2024-03-01T10:00:00+01:00");
+        assert_eq!(hello.first_commit.hash, "aaa");
+        assert_eq!(hello.first_commit.date, "2024-01-01T10:00:00Z");
+        assert_eq!(hello.authors, vec!["Bob".to_string(), "Alice Doe".to_string()]);
+
+        let about = &history["about.md"];
+        assert_eq!(about.last_commit, about.first_commit);
+        assert_eq!(about.authors, vec!["Alice Doe".to_string()]);
+    }
+
+    #[test]
+    fn can_read_history_of_local_repository() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let root = tmp_dir.path();
+        let content = root.join("content");
+        std::fs::create_dir_all(content.join("blog")).unwrap();
+        std::fs::write(content.join("blog").join("hello.md"), "+++\n+++\n").unwrap();
+        std::fs::write(content.join("draft.md"), "+++\n+++\n").unwrap();
+
+        let git = |args: &[&str]| {
+            let mut full_args = vec!["-c", "user.name=Jane", "-c", "user.email=jane@example.com"];
+            full_args.extend(args);
+            run_git(root, &full_args).unwrap();
+        };
+        git(&["init", "-q"]);
+        git(&["add", "content/blog/hello.md"]);
+        git(&["commit", "-q", "-m", "Add hello", "--date", "2024-01-01T10:00:00Z"]);
+
+        let history = read_git_history(&content).unwrap();
+        assert_eq!(history.len(), 1);
+        let hello = &history["blog/hello.md"];
+        assert_eq!(hello.first_commit.date, "2024-01-01T10:00:00+00:00");
+        assert_eq!(hello.authors, vec!["Jane".to_string()]);
+    }
+}
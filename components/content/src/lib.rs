@@ -1,6 +1,10 @@
 mod front_matter;
 
+mod archives;
+mod authors;
+
 mod file_info;
+mod git;
 mod library;
 mod page;
 mod pagination;
@@ -11,11 +15,17 @@ mod taxonomies;
 mod types;
 mod utils;
 
+pub use archives::{Archive, SerializedArchive};
+pub use authors::{Author, SerializedAuthor};
 pub use file_info::FileInfo;
-pub use front_matter::{PageFrontMatter, SectionFrontMatter};
+pub use front_matter::{
+    parse_datetime, FieldSchema, FieldType, PageFrontMatter, PageSchema, SectionFrontMatter,
+};
+pub use git::{read_git_history, GitCommit, GitInfo};
 pub use library::Library;
 pub use page::Page;
 pub use pagination::Paginator;
 pub use section::Section;
+pub use sorting::sort_pages;
 pub use taxonomies::{Taxonomy, TaxonomyTerm};
 pub use types::*;
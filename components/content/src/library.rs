@@ -1,8 +1,12 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use config::Config;
 use libs::ahash::{AHashMap, AHashSet};
+use utils::site::WikiLinkTargets;
 
+use crate::archives::Archive;
+use crate::authors::Author;
 use crate::ser::TranslatedContent;
 use crate::sorting::sort_pages;
 use crate::taxonomies::{Taxonomy, TaxonomyFound};
@@ -30,6 +34,10 @@ pub struct Library {
     pub reverse_aliases: AHashMap<String, AHashSet<PathBuf>>,
     pub translations: AHashMap<PathBuf, AHashSet<PathBuf>>,
     pub backlinks: AHashMap<String, AHashSet<PathBuf>>,
+    /// All the authors from `[authors]` in the config, one per language
+    pub authors: Vec<Author>,
+    /// The yearly and monthly archives of the section set in `[archive]`, for each language
+    pub archives: Vec<Archive>,
     // A mapping of {lang -> <slug, {term -> vec<paths>}>>}
     taxonomies_def: AHashMap<String, AHashMap<String, AHashMap<String, Vec<PathBuf>>>>,
     // All the taxonomies from config.toml in their slugifiedv ersion
@@ -88,7 +96,9 @@ impl Library {
             self.insert_reverse_aliases(&file_path, entries);
         }
 
-        for (taxa_name, terms) in &page.meta.taxonomies {
+        // Unlisted pages are not part of any taxonomy term
+        let taxonomies = if page.meta.unlisted { None } else { Some(&page.meta.taxonomies) };
+        for (taxa_name, terms) in taxonomies.into_iter().flatten() {
             for term in terms {
                 // Safe unwraps as we create all lang/taxa and we validated that they are correct
                 // before getting there
@@ -119,6 +129,44 @@ impl Library {
         self.sections.insert(file_path, section);
     }
 
+    /// Finds what `[[Title]]` links can point to: pages by title, slug and file name, and sections
+    /// by title and directory name
+    pub fn wiki_link_targets(&self) -> WikiLinkTargets {
+        let mut targets = WikiLinkTargets::default();
+        for page in self.pages.values() {
+            let mut names = vec![page.slug.as_str()];
+            if let Some(ref title) = page.meta.title {
+                names.push(title);
+            }
+            if page.file.name != "index" {
+                names.push(&page.file.name);
+            }
+            targets.insert(&page.lang, &names, &page.file.relative);
+        }
+        for section in self.sections.values() {
+            let mut names = vec![];
+            if let Some(ref title) = section.meta.title {
+                names.push(title.as_str());
+            }
+            if let Some(dir_name) = section.file.components.last() {
+                names.push(dir_name);
+            }
+            targets.insert(&section.lang, &names, &section.file.relative);
+        }
+        targets
+    }
+
+    /// Finds the titles of the pages and sections by relative path, used to fill internal links
+    /// without text
+    pub fn titles(&self) -> HashMap<String, String> {
+        let pages = self.pages.values().map(|p| (&p.file.relative, &p.meta.title));
+        let sections = self.sections.values().map(|s| (&s.file.relative, &s.meta.title));
+        pages
+            .chain(sections)
+            .filter_map(|(relative, title)| Some((relative.clone(), title.clone()?)))
+            .collect()
+    }
+
     /// Fills a map of target -> {content mentioning it}
     /// This can only be called _after_ rendering markdown as we need to have accumulated all
     /// the links first
@@ -171,6 +219,60 @@ impl Library {
         taxonomies
     }
 
+    /// Finds the pages of every author defined in the config, in each language.
+    /// This is called _before_ rendering the markdown the pages/sections
+    pub fn populate_authors(&mut self, config: &Config) {
+        let mut authors = Vec::new();
+        for lang in config.languages.keys() {
+            for id in config.authors.keys() {
+                let pages: Vec<_> = self
+                    .pages
+                    .values()
+                    .filter(|p| &p.lang == lang && !p.meta.unlisted && p.meta.authors.contains(id))
+                    .collect();
+                authors.push(Author::new(id, lang, &pages, config));
+            }
+        }
+        authors.sort_by(|a, b| a.lang.cmp(&b.lang).then_with(|| a.id.cmp(&b.id)));
+        self.authors = authors;
+    }
+
+    pub fn find_author(&self, id: &str, lang: &str) -> Option<&Author> {
+        self.authors.iter().find(|a| a.id == id && a.lang == lang)
+    }
+
+    /// Groups the pages of the section set in `[archive]` by date, in each language having
+    /// that section.
+    /// This can only be called _after_ `populate_sections` as we need the pages of the sections
+    pub fn populate_archives(&mut self, config: &Config, content_path: &Path) {
+        self.archives.clear();
+        let archive_config = match config.archive {
+            Some(ref a) => a,
+            None => return,
+        };
+
+        let mut languages: Vec<_> = config.languages.keys().collect();
+        languages.sort();
+        for lang in languages {
+            let filename = if lang == &config.default_language {
+                "_index.md".to_string()
+            } else {
+                format!("_index.{}.md", lang)
+            };
+            let section_path = content_path.join(&archive_config.section).join(filename);
+            if let Some(section) = self.sections.get(&section_path) {
+                let pages: Vec<_> = section.pages.iter().map(|p| &self.pages[p]).collect();
+                self.archives.extend(Archive::find_all(
+                    &section_path,
+                    lang,
+                    &pages,
+                    config,
+                    archive_config,
+                ));
+            }
+        }
+    }
+
     /// Sort all sections pages according to sorting method given
     /// Pages that cannot be sorted are set to the section.ignored_pages instead
     pub fn sort_section_pages(&mut self) {
@@ -240,7 +342,7 @@ impl Library {
                     .push(section.file.path.clone());
             }
 
-            add_translation(&section.file.canonical, path);
+            add_translation(&section.translation_id(), path);
 
             // Root sections have no ancestors
             if section.is_index() {
@@ -299,12 +401,15 @@ impl Library {
                 continue;
             }
             let parent_filename = &index_filename_by_lang[&page.lang];
-            add_translation(&page.file.canonical, path);
+            add_translation(&page.translation_id(), path);
             let mut parent_section_path = page.file.parent.join(parent_filename);
 
             while let Some(parent_section) = self.sections.get_mut(&parent_section_path) {
                 let is_transparent = parent_section.meta.transparent;
-                parent_section.pages.push(path.clone());
+                // Unlisted pages still get their ancestors but are not listed in the section
+                if !page.meta.unlisted {
+                    parent_section.pages.push(path.clone());
+                }
                 page.ancestors = ancestors.get(&parent_section_path).cloned().unwrap_or_default();
                 // Don't forget to push the actual parent
                 page.ancestors.push(parent_section.file.relative.clone());
@@ -338,12 +443,71 @@ impl Library {
         self.sort_section_pages();
     }
 
+    /// Validates the front matter of every page against the `page_schema` of its closest
+    /// ancestor section declaring one.
+    /// This can only be called _after_ `populate_sections` as we need the ancestors.
+    /// Returns Vec<(page path, [list of violations])>, sorted by path
+    pub fn find_schema_violations(&self, content_path: &Path) -> Vec<(PathBuf, Vec<String>)> {
+        let mut violations: Vec<_> = self
+            .pages
+            .values()
+            .filter_map(|page| {
+                let schema = page.ancestors.iter().rev().find_map(|ancestor| {
+                    self.sections
+                        .get(&content_path.join(ancestor))
+                        .and_then(|s| s.meta.page_schema.as_ref())
+                })?;
+                let errors = schema.validate(&page.meta);
+                if errors.is_empty() {
+                    None
+                } else {
+                    Some((page.file.path.clone(), errors))
+                }
+            })
+            .collect();
+        violations.sort();
+        violations
+    }
+
     /// Find all the orphan pages: pages that are in a folder without an `_index.md`
     pub fn get_all_orphan_pages(&self) -> Vec<&Page> {
         self.pages.iter().filter(|(_, p)| p.ancestors.is_empty()).map(|(_, p)| p).collect()
     }
 
-    /// Find all the translated content for a given canonical path.
+    /// Find all the unlisted pages that belong to a section: they are not rendered
+    /// with the pages of their section
+    pub fn get_all_unlisted_pages(&self) -> Vec<&Page> {
+        self.pages
+            .iter()
+            .filter(|(_, p)| p.meta.unlisted && !p.ancestors.is_empty())
+            .map(|(_, p)| p)
+            .collect()
+    }
+
+    /// Finds the pages sharing a `translation_key` in the same language, which would make
+    /// their translations ambiguous.
+    /// Returns Vec<(translation_key, [list of pages])>, sorted by key
+    pub fn find_translation_key_conflicts(&self) -> Vec<(String, Vec<PathBuf>)> {
+        let mut by_key: AHashMap<(&str, &str), Vec<PathBuf>> = AHashMap::new();
+        for page in self.pages.values() {
+            if let Some(ref key) = page.meta.translation_key {
+                by_key.entry((key, &page.lang)).or_default().push(page.file.path.clone());
+            }
+        }
+        let mut conflicts: Vec<_> = by_key
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .map(|((key, _), mut paths)| {
+                paths.sort();
+                (key.to_string(), paths)
+            })
+            .collect();
+        conflicts.sort();
+        conflicts
+    }
+
+    /// Find all the translated content for a given translation id: the canonical path or the
+    /// `translation_key` of the content, see `Page::translation_id`.
     /// The translated content can be either for a section or a page
     pub fn find_translations(&self, canonical_path: &Path) -> Vec<TranslatedContent<'_>> {
         let mut translations = vec![];
@@ -613,6 +777,122 @@ mod tests {
         assert!(translations[1].title.is_some());
     }
 
+    #[test]
+    fn unlisted_pages_are_not_in_sections() {
+        let config = Config::default_for_test();
+        let mut library = Library::default();
+        library.insert_section(create_section("content/_index.md", "en", 0, false, SortBy::None));
+        library.insert_section(create_section(
+            "content/blog/_index.md",
+            "en",
+            0,
+            false,
+            SortBy::Date,
+        ));
+        library.insert_page(create_page(
+            "content/blog/rust.md",
+            "en",
+            PageSort::Date("2022-01-01"),
+        ));
+        library.insert_page(create_page(
+            "content/blog/zola.md",
+            "en",
+            PageSort::Date("2022-03-03"),
+        ));
+        let mut secret = create_page("content/blog/secret.md", "en", PageSort::Date("2022-02-02"));
+        secret.meta.unlisted = true;
+        library.insert_page(secret);
+        library.populate_sections(&config, Path::new("content"));
+
+        let blog_section = &library.sections[&PathBuf::from("content/blog/_index.md")];
+        assert_eq!(
+            blog_section.pages,
+            vec![PathBuf::from("content/blog/zola.md"), PathBuf::from("content/blog/rust.md")]
+        );
+        // Neighbours skip the unlisted page
+        assert_eq!(
+            library.pages[&PathBuf::from("content/blog/zola.md")].higher,
+            Some(PathBuf::from("content/blog/rust.md"))
+        );
+        let secret = &library.pages[&PathBuf::from("content/blog/secret.md")];
+        assert_eq!(secret.ancestors, vec!["_index.md".to_owned(), "blog/_index.md".to_owned()]);
+        assert!(secret.lower.is_none() && secret.higher.is_none());
+        assert!(library.get_all_orphan_pages().is_empty());
+        assert_eq!(library.get_all_unlisted_pages().len(), 1);
+    }
+
+    #[test]
+    fn can_link_translations_by_key() {
+        let mut config = Config::default_for_test();
+        config.languages.insert("fr".to_owned(), LanguageOptions::default());
+        let mut library = Library::default();
+        library.insert_section(create_section("content/_index.md", "en", 0, false, SortBy::None));
+        let mut about = create_page("content/about.md", "en", PageSort::None);
+        about.meta.translation_key = Some("about".to_owned());
+        library.insert_page(about);
+        let mut a_propos = create_page("content/a-propos.fr.md", "fr", PageSort::None);
+        a_propos.meta.translation_key = Some("about".to_owned());
+        library.insert_page(a_propos);
+        library.insert_page(create_page("content/contact.md", "en", PageSort::None));
+        library.populate_sections(&config, Path::new("content"));
+
+        let about = &library.pages[&PathBuf::from("content/about.md")];
+        let mut translations: Vec<_> =
+            library.find_translations(&about.translation_id()).iter().map(|t| t.path).collect();
+        translations.sort();
+        assert_eq!(
+            translations,
+            vec![Path::new("content/a-propos.fr.md"), Path::new("content/about.md")]
+        );
+        let contact = &library.pages[&PathBuf::from("content/contact.md")];
+        assert_eq!(library.find_translations(&contact.translation_id()).len(), 1);
+        assert!(library.find_translation_key_conflicts().is_empty());
+
+        let mut other = create_page("content/other.fr.md", "fr", PageSort::None);
+        other.meta.translation_key = Some("about".to_owned());
+        library.insert_page(other);
+        assert_eq!(
+            library.find_translation_key_conflicts(),
+            vec![(
+                "about".to_owned(),
+                vec![PathBuf::from("content/a-propos.fr.md"), PathBuf::from("content/other.fr.md")]
+            )]
+        );
+    }
+
+    #[test]
+    fn can_find_schema_violations() {
+        let config = Config::default_for_test();
+        let mut library = Library::default();
+        let mut blog = create_section("content/blog/_index.md", "en", 0, false, SortBy::None);
+        blog.meta.page_schema =
+            Some(libs::toml::from_str(r#""extra.hero_image" = { required = true }"#).unwrap());
+        library.insert_section(create_section("content/_index.md", "en", 0, false, SortBy::None));
+        library.insert_section(blog);
+        library.insert_section(create_section(
+            "content/blog/2024/_index.md",
+            "en",
+            0,
+            true,
+            SortBy::None,
+        ));
+        let mut valid = create_page("content/blog/valid.md", "en", PageSort::None);
+        valid.meta.extra.insert("hero_image".to_owned(), "hero.png".into());
+        library.insert_page(valid);
+        library.insert_page(create_page("content/blog/2024/invalid.md", "en", PageSort::None));
+        library.insert_page(create_page("content/about.md", "en", PageSort::None));
+        library.populate_sections(&config, Path::new("content"));
+
+        let violations = library.find_schema_violations(Path::new("content"));
+        assert_eq!(
+            violations,
+            vec![(
+                PathBuf::from("content/blog/2024/invalid.md"),
+                vec!["`extra.hero_image` is required".to_owned()]
+            )]
+        );
+    }
+
     macro_rules! taxonomies {
         ($config:expr, [$($page:expr),+]) => {{
             let mut library = Library::new(&$config);
@@ -623,6 +903,37 @@ mod tests {
         }};
     }
 
+    #[test]
+    fn can_find_wiki_link_targets() {
+        let mut library = Library::default();
+        library.insert_section(create_section("content/_index.md", "en", 0, false, SortBy::None));
+        let mut docs = create_section("content/docs/_index.md", "en", 0, false, SortBy::None);
+        docs.meta.title = Some("Documentation".to_owned());
+        library.insert_section(docs);
+        let mut about = create_page("content/about.md", "en", PageSort::Title("About us"));
+        about.slug = "about".to_owned();
+        library.insert_page(about);
+        let mut a_propos = create_page("content/about.fr.md", "fr", PageSort::None);
+        a_propos.slug = "a-propos".to_owned();
+        library.insert_page(a_propos);
+
+        let targets = library.wiki_link_targets();
+        let mut permalinks = HashMap::new();
+        for path in ["about.md", "about.fr.md", "docs/_index.md"] {
+            permalinks.insert(path.to_owned(), format!("https://example.com/{}", path));
+        }
+        let resolve = |name: &str, lang: &str| {
+            targets.resolve(name, lang, &permalinks, SlugifyStrategy::On).map(|r| r.md_path)
+        };
+        assert_eq!(resolve("about us", "en").unwrap(), "about.md");
+        assert_eq!(resolve("About", "en").unwrap(), "about.md");
+        assert_eq!(resolve("a-propos", "fr").unwrap(), "about.fr.md");
+        assert_eq!(resolve("about", "fr").unwrap(), "about.fr.md");
+        assert_eq!(resolve("Documentation", "en").unwrap(), "docs/_index.md");
+        assert_eq!(resolve("docs", "en").unwrap(), "docs/_index.md");
+        assert!(resolve("a-propos", "en").is_err());
+    }
+
     fn create_page_w_taxa(path: &str, lang: &str, taxo: Vec<(&str, Vec<&str>)>) -> Page {
         let mut page = Page::default();
         page.file.path = PathBuf::from(path);
@@ -3,23 +3,25 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use libs::once_cell::sync::Lazy;
-use libs::regex::Regex;
-use libs::tera::{Context as TeraContext, Tera};
+use libs::regex::{Captures, Regex};
+use libs::tera::{Context as TeraContext, Tera, Value};
 
 use config::Config;
-use errors::{Context, Result};
+use errors::{bail, Context, Result};
 use markdown::{render_content, RenderContext};
+use utils::site::WikiLinkTargets;
 use utils::slugs::slugify_paths;
 use utils::table_of_contents::Heading;
 use utils::templates::{render_template, ShortcodeDefinition};
-use utils::types::InsertAnchor;
+use utils::types::{AutoSummary, InsertAnchor};
 
 use crate::file_info::FileInfo;
 use crate::front_matter::{split_page_content, PageFrontMatter};
+use crate::git::GitInfo;
 use crate::library::Library;
 use crate::ser::SerializingPage;
-use crate::utils::get_reading_analytics;
-use crate::utils::{find_related_assets, has_anchor};
+use crate::utils::{find_related_assets, has_anchor, translation_id};
+use crate::utils::{get_reading_analytics, lines_before_content};
 use utils::anchors::has_anchor_id;
 use utils::fs::read_file;
 
@@ -31,6 +33,10 @@ static RFC3339_DATE: Lazy<Regex> = Lazy::new(|| {
     ).unwrap()
 });
 
+// A token of a permalink pattern, eg `:year` or `:extra.category`
+static PERMALINK_TOKEN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r":([a-z]+(?:\.[A-Za-z0-9_-]+)?)").unwrap());
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Page {
     /// All info about the actual file
@@ -41,6 +47,8 @@ pub struct Page {
     pub ancestors: Vec<String>,
     /// The actual content of the page, in markdown
     pub raw_content: String,
+    /// The number of lines of the file before `raw_content`, which are its front matter
+    pub content_line_offset: usize,
     /// All the non-md files we found next to the .md file
     pub assets: Vec<PathBuf>,
     /// All the non-md files we found next to the .md file
@@ -82,6 +90,11 @@ pub struct Page {
     pub internal_links: Vec<(String, Option<String>)>,
     /// The list of all links to external webpages. They can be validated by the `link_checker`.
     pub external_links: Vec<String>,
+    /// What the local git history knows about that page, only set if `use_git_history` is enabled
+    pub git: Option<GitInfo>,
+    /// Whether this is a copy of a page of the default language standing in for its missing
+    /// translation, only created if `translation_fallback` is enabled for the language
+    pub is_fallback: bool,
 }
 
 impl Page {
@@ -100,14 +113,15 @@ impl Page {
         config: &Config,
         base_path: &Path,
     ) -> Result<Page> {
-        let (meta, content) = split_page_content(file_path, content)?;
+        let (meta, raw_content) = split_page_content(file_path, content)?;
         let mut page = Page::new(file_path, meta, base_path);
 
         page.lang =
             page.file.find_language(&config.default_language, &config.other_languages_codes())?;
 
-        page.raw_content = content.to_string();
-        let (word_count, reading_time) = get_reading_analytics(&page.raw_content);
+        page.raw_content = raw_content.to_string();
+        page.content_line_offset = lines_before_content(content, raw_content);
+        let (word_count, reading_time) = get_reading_analytics(&page.raw_content, config);
         page.word_count = Some(word_count);
         page.reading_time = Some(reading_time);
 
@@ -169,19 +183,71 @@ impl Page {
             format!("/{}", path)
         };
 
-        if !page.path.ends_with('/') {
-            page.path = format!("{}/", page.path);
-        }
+        page.set_path(page.path.clone(), config);
+
+        Ok(page)
+    }
 
-        page.components = page
+    /// Sets the path of the page along with the components and permalink depending on it
+    fn set_path(&mut self, path: String, config: &Config) {
+        self.path = if path.ends_with('/') { path } else { format!("{}/", path) };
+        self.components = self
             .path
             .split('/')
             .map(|p| p.to_string())
             .filter(|p| !p.is_empty())
             .collect::<Vec<_>>();
-        page.permalink = config.make_permalink(&page.path);
+        self.permalink = config.make_permalink(&self.path);
+    }
 
-        Ok(page)
+    /// Replaces the path of the page with one built from a permalink pattern such as
+    /// `/:year/:month/:slug/`. Pages with a `path` in their front matter are left untouched.
+    /// This needs to be called once the date of the page is known.
+    pub fn apply_permalink_pattern(&mut self, pattern: &str, config: &Config) -> Result<()> {
+        if self.meta.path.is_some() {
+            return Ok(());
+        }
+
+        let mut error = None;
+        let path = PERMALINK_TOKEN.replace_all(pattern, |caps: &Captures| {
+            let token = &caps[1];
+            let date = self.meta.datetime_tuple;
+            let value = match token {
+                "slug" => Some(self.slug.clone()),
+                "section" => Some(self.file.components.join("/")),
+                "lang" => Some(self.lang.clone()),
+                "year" => date.map(|(y, _, _)| format!("{:04}", y)),
+                "month" => date.map(|(_, m, _)| format!("{:02}", m)),
+                "day" => date.map(|(_, _, d)| format!("{:02}", d)),
+                _ => match token.strip_prefix("extra.").and_then(|k| self.meta.extra.get(k)) {
+                    Some(Value::String(s)) => Some(slugify_paths(s, config.slugify.paths)),
+                    Some(Value::Number(n)) => Some(n.to_string()),
+                    _ => None,
+                },
+            };
+            value.unwrap_or_else(|| {
+                error.get_or_insert_with(|| token.to_string());
+                String::new()
+            })
+        });
+
+        if let Some(token) = error {
+            bail!(
+                "Page `{}` cannot use the permalink pattern `{}`: `:{}` is not a known token or has no value for this page",
+                self.file.path.display(),
+                pattern,
+                token
+            );
+        }
+
+        // Empty tokens, like the section of a page at the root, should not leave double slashes
+        let mut path = path.split('/').filter(|c| !c.is_empty()).collect::<Vec<_>>().join("/");
+        if self.lang != config.default_language && !pattern.contains(":lang") {
+            path = format!("{}/{}", self.lang, path);
+        }
+        self.set_path(format!("/{}", path), config);
+
+        Ok(())
     }
 
     pub fn find_language(&mut self) {}
@@ -203,14 +269,54 @@ impl Page {
         Ok(page)
     }
 
+    /// What links the page to its translations in `Library::translations`
+    pub fn translation_id(&self) -> PathBuf {
+        translation_id(&self.file.canonical, &self.meta.translation_key)
+    }
+
+    /// Creates a copy of this page, from the default language, to stand in for its missing
+    /// translation in `lang`. The copy gets the file path the translation would have
+    /// (eg `about.fr.md`) so it is found as a translation of this page.
+    pub fn as_translation_fallback(&self, lang: &str, config: &Config) -> Page {
+        let mut page = self.clone();
+        page.lang = lang.to_string();
+        page.is_fallback = true;
+        page.file.filename = format!("{}.{}.md", self.file.name, lang);
+        page.file.path = self.file.path.with_file_name(&page.file.filename);
+        page.file.relative = match self.file.relative.rsplit_once('/') {
+            Some((dir, _)) => format!("{}/{}", dir, page.file.filename),
+            None => page.file.filename.clone(),
+        };
+        // Taxonomies are defined per language
+        page.meta.taxonomies.retain(|name, _| config.has_taxonomy(name, lang));
+        page.set_path(format!("/{}{}", lang, self.path), config);
+        page
+    }
+
+    /// Uses the git history of the file for the `date` and `updated` not set in the front matter
+    pub fn set_git_info(&mut self, info: GitInfo) {
+        if self.meta.date.is_none() {
+            self.meta.date = Some(info.first_commit.date.clone());
+        }
+        if self.meta.updated.is_none() {
+            self.meta.updated = Some(info.last_commit.date.clone());
+        }
+        self.meta.date_to_datetime();
+        self.git = Some(info);
+    }
+
     /// We need access to all pages url to render links relative to content
     /// so that can't happen at the same time as parsing
+    #[allow(clippy::too_many_arguments)]
     pub fn render_markdown(
         &mut self,
         permalinks: &HashMap<String, String>,
+        wiki_link_targets: &WikiLinkTargets,
+        titles: &HashMap<String, String>,
         tera: &Tera,
         config: &Config,
         anchor_insert: InsertAnchor,
+        auto_summary: AutoSummary,
         shortcode_definitions: &HashMap<String, ShortcodeDefinition>,
     ) -> Result<()> {
         let mut context = RenderContext::new(
@@ -223,6 +329,12 @@ impl Page {
         );
         context.set_shortcode_definitions(shortcode_definitions);
         context.set_current_page_path(&self.file.relative);
+        context.set_wiki_link_targets(wiki_link_targets);
+        context.set_titles(titles);
+        if self.meta.glossary {
+            context.set_glossary(config.markdown.glossaries.get(&self.lang));
+        }
+        context.set_auto_summary(auto_summary);
         context.tera_context.insert("page", &SerializingPage::new(self, None, false));
 
         let res = render_content(&self.raw_content, &context)
@@ -306,10 +418,11 @@ mod tests {
     use tempfile::tempdir;
     use templates::ZOLA_TERA;
 
-    use crate::Page;
+    use crate::{GitCommit, GitInfo, Page};
     use config::{Config, LanguageOptions};
+    use utils::site::WikiLinkTargets;
     use utils::slugs::SlugifyStrategy;
-    use utils::types::InsertAnchor;
+    use utils::types::{AutoSummary, InsertAnchor};
 
     #[test]
     fn can_parse_a_valid_page() {
@@ -326,9 +439,12 @@ Hello world"#;
         let mut page = res.unwrap();
         page.render_markdown(
             &HashMap::default(),
+            &WikiLinkTargets::default(),
+            &HashMap::new(),
             &ZOLA_TERA,
             &config,
             InsertAnchor::None,
+            AutoSummary::None,
             &HashMap::new(),
         )
         .unwrap();
@@ -336,6 +452,7 @@ Hello world"#;
         assert_eq!(page.meta.title.unwrap(), "Hello".to_string());
         assert_eq!(page.meta.slug.unwrap(), "hello-world".to_string());
         assert_eq!(page.raw_content, "Hello world".to_string());
+        assert_eq!(page.content_line_offset, 6);
         assert_eq!(page.content, "<p>Hello world</p>\n".to_string());
     }
 
@@ -354,9 +471,12 @@ Hello world"#;
         let mut page = res.unwrap();
         page.render_markdown(
             &HashMap::default(),
+            &WikiLinkTargets::default(),
+            &HashMap::new(),
             &ZOLA_TERA,
             &config,
             InsertAnchor::None,
+            AutoSummary::None,
             &HashMap::new(),
         )
         .unwrap();
@@ -510,6 +630,86 @@ Hello world"#;
         assert_eq!(page.permalink, config.make_permalink(&page.slug));
     }
 
+    #[test]
+    fn can_apply_permalink_pattern() {
+        let content = r#"
++++
+date = 2024-05-03
+[extra]
+category = "Rust Tips"
++++
+Hello world"#;
+        let mut config = Config::default_for_test();
+        config.languages.insert("fr".to_owned(), LanguageOptions::default());
+        let mut page = Page::parse(
+            Path::new("content/posts/2024-05-03-hello.md"),
+            content,
+            &config,
+            &PathBuf::new(),
+        )
+        .unwrap();
+        page.apply_permalink_pattern("/:section/:year/:month/:day/:slug", &config).unwrap();
+        assert_eq!(page.path, "/posts/2024/05/03/hello/");
+        assert_eq!(page.components, vec!["posts", "2024", "05", "03", "hello"]);
+        assert_eq!(page.permalink, config.make_permalink("posts/2024/05/03/hello"));
+
+        page.apply_permalink_pattern("/:extra.category/:slug/", &config).unwrap();
+        assert_eq!(page.path, "/rust-tips/hello/");
+
+        // Other languages are prefixed unless the pattern places the language itself
+        page.lang = "fr".to_owned();
+        page.apply_permalink_pattern("/:year/:slug/", &config).unwrap();
+        assert_eq!(page.path, "/fr/2024/hello/");
+        page.apply_permalink_pattern("/blog/:lang/:slug/", &config).unwrap();
+        assert_eq!(page.path, "/blog/fr/hello/");
+    }
+
+    #[test]
+    fn permalink_pattern_does_not_override_path() {
+        let content = "+++\npath = \"about-me\"\n+++\n";
+        let config = Config::default_for_test();
+        let mut page =
+            Page::parse(Path::new("content/about.md"), content, &config, &PathBuf::new()).unwrap();
+        page.apply_permalink_pattern("/:year/:slug/", &config).unwrap();
+        assert_eq!(page.path, "/about-me/");
+    }
+
+    #[test]
+    fn can_create_translation_fallback() {
+        let mut config = Config::default_for_test();
+        config.languages.insert("fr".to_owned(), LanguageOptions::default());
+        let content = "+++\n[taxonomies]\ntags = [\"rust\"]\n+++\nHello";
+        let page = Page::parse(Path::new("content/blog/hello.md"), content, &config, Path::new(""))
+            .unwrap();
+        let fallback = page.as_translation_fallback("fr", &config);
+
+        assert!(fallback.is_fallback);
+        assert_eq!(fallback.lang, "fr");
+        assert_eq!(fallback.path, "/fr/blog/hello/");
+        assert_eq!(fallback.permalink, config.make_permalink("fr/blog/hello"));
+        assert_eq!(fallback.file.path, PathBuf::from("content/blog/hello.fr.md"));
+        assert_eq!(fallback.file.relative, "blog/hello.fr.md");
+        assert_eq!(fallback.translation_id(), page.translation_id());
+        assert_eq!(fallback.raw_content, page.raw_content);
+        // `tags` is not defined for French
+        assert!(fallback.meta.taxonomies.is_empty());
+    }
+
+    #[test]
+    fn errors_on_permalink_pattern_without_value() {
+        let config = Config::default_for_test();
+        let mut page =
+            Page::parse(Path::new("content/about.md"), "+++\n+++\n", &config, &PathBuf::new())
+                .unwrap();
+        let err = page.apply_permalink_pattern("/:year/:slug/", &config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Page `content/about.md` cannot use the permalink pattern `/:year/:slug/`: `:year` is not a known token or has no value for this page"
+        );
+        assert!(page.apply_permalink_pattern("/:title/", &config).is_err());
+        assert!(page.apply_permalink_pattern("/:extra.missing/", &config).is_err());
+    }
+
     #[test]
     fn can_specify_summary() {
         let config = Config::default_for_test();
@@ -524,9 +724,12 @@ Hello world
         let mut page = res.unwrap();
         page.render_markdown(
             &HashMap::default(),
+            &WikiLinkTargets::default(),
+            &HashMap::new(),
             &ZOLA_TERA,
             &config,
             InsertAnchor::None,
+            AutoSummary::None,
             &HashMap::new(),
         )
         .unwrap();
@@ -558,9 +761,12 @@ And here's another. [^3]
         let mut page = res.unwrap();
         page.render_markdown(
             &HashMap::default(),
+            &WikiLinkTargets::default(),
+            &HashMap::new(),
             &ZOLA_TERA,
             &config,
             InsertAnchor::None,
+            AutoSummary::None,
             &HashMap::new(),
         )
         .unwrap();
@@ -934,4 +1140,31 @@ Bonjour le monde"#
         assert_eq!(page.slug, "hello");
         assert_eq!(page.permalink, "http://a-website.com/bonjour/");
     }
+
+    #[test]
+    fn git_info_does_not_override_front_matter_dates() {
+        let config = Config::default();
+        let content = r#"
++++
+date = 2018-01-01
++++
+Hello world"#
+            .to_string();
+        let mut page =
+            Page::parse(Path::new("hello.md"), &content, &config, &PathBuf::new()).unwrap();
+        let commit = |hash: &str, date: &str| GitCommit {
+            hash: hash.to_string(),
+            date: date.to_string(),
+            author: "Jane".to_string(),
+        };
+        page.set_git_info(GitInfo {
+            last_commit: commit("bbb", "2024-02-01T10:00:00+01:00"),
+            first_commit: commit("aaa", "2024-01-01T10:00:00+01:00"),
+            authors: vec!["Jane".to_string()],
+        });
+        assert_eq!(page.meta.date, Some("2018-01-01".to_string()));
+        assert_eq!(page.meta.updated, Some("2024-02-01T10:00:00+01:00".to_string()));
+        assert_eq!(page.meta.updated_datetime_tuple, Some((2024, 2, 1)));
+        assert_eq!(page.git.unwrap().last_commit.hash, "bbb");
+    }
 }
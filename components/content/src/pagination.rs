@@ -8,6 +8,7 @@ use errors::{Context as ErrorContext, Result};
 use libs::tera::{to_value, Context, Tera, Value};
 use utils::templates::{check_template_fallbacks, render_template};
 
+use crate::archives::Archive;
 use crate::library::Library;
 use crate::ser::{SectionSerMode, SerializingPage, SerializingSection};
 use crate::taxonomies::{Taxonomy, TaxonomyTerm};
@@ -17,6 +18,7 @@ use crate::Section;
 enum PaginationRoot<'a> {
     Section(&'a Section),
     Taxonomy(&'a Taxonomy, &'a TaxonomyTerm),
+    Archive(&'a Archive),
 }
 
 /// A list of all the pages in the paginator with their index and links
@@ -118,6 +120,32 @@ impl<'a> Paginator<'a> {
         paginator
     }
 
+    /// Create a new paginator from an archive, using the pagination settings of `[archive]`
+    /// It will always at least create one pager (the first) even if there are not enough pages to paginate
+    pub fn from_archive(
+        archive: &'a Archive,
+        library: &'a Library,
+        config: &Config,
+    ) -> Paginator<'a> {
+        let archive_config = config.archive.as_ref().expect("archives require `[archive]`");
+        let paginate_by = archive_config.paginate_by.unwrap();
+        let mut paginator = Paginator {
+            all_pages: Cow::Borrowed(&archive.pages),
+            pagers: Vec::with_capacity(archive.pages.len() / paginate_by),
+            paginate_by,
+            paginate_reversed: false,
+            root: PaginationRoot::Archive(archive),
+            permalink: archive.permalink.clone(),
+            path: archive.path.clone(),
+            paginate_path: archive_config.paginate_path.clone(),
+            is_index: false,
+            template: "archive.html".to_string(),
+        };
+
+        paginator.fill_pagers(library);
+        paginator
+    }
+
     fn fill_pagers(&mut self, library: &'a Library) {
         // the list of pagers
         let mut pages = vec![];
@@ -241,6 +269,12 @@ impl<'a> Paginator<'a> {
                 context.insert("lang", &t.lang);
                 context.insert("config", &config.serialize(&t.lang));
             }
+            PaginationRoot::Archive(a) => {
+                context.insert("archive", &a.serialize_without_pages(library));
+                context.insert("section", &library.sections[&a.section].serialize_basic(library));
+                context.insert("lang", &a.lang);
+                context.insert("config", &config.serialize(&a.lang));
+            }
         };
         context.insert("current_url", &pager.permalink);
         context.insert("current_path", &pager.path);
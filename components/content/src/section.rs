@@ -8,6 +8,7 @@ use errors::{Context, Result};
 use markdown::{render_content, RenderContext};
 use utils::fs::read_file;
 use utils::net::is_external_link;
+use utils::site::WikiLinkTargets;
 use utils::table_of_contents::Heading;
 use utils::templates::{render_template, ShortcodeDefinition};
 
@@ -15,7 +16,9 @@ use crate::file_info::FileInfo;
 use crate::front_matter::{split_section_content, SectionFrontMatter};
 use crate::library::Library;
 use crate::ser::{SectionSerMode, SerializingSection};
-use crate::utils::{find_related_assets, get_reading_analytics, has_anchor};
+use crate::utils::{
+    find_related_assets, get_reading_analytics, has_anchor, lines_before_content, translation_id,
+};
 
 // Default is used to create a default index section if there is no _index.md in the root content directory
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -32,6 +35,8 @@ pub struct Section {
     pub permalink: String,
     /// The actual content of the page, in markdown
     pub raw_content: String,
+    /// The number of lines of the file before `raw_content`, which are its front matter
+    pub content_line_offset: usize,
     /// The HTML rendered of the page
     pub content: String,
     /// All the non-md files we found next to the .md file
@@ -81,13 +86,14 @@ impl Section {
         config: &Config,
         base_path: &Path,
     ) -> Result<Section> {
-        let (meta, content) = split_section_content(file_path, content)?;
+        let (meta, raw_content) = split_section_content(file_path, content)?;
         let mut section = Section::new(file_path, meta, base_path);
         section.lang = section
             .file
             .find_language(&config.default_language, &config.other_languages_codes())?;
-        section.raw_content = content.to_string();
-        let (word_count, reading_time) = get_reading_analytics(&section.raw_content);
+        section.raw_content = raw_content.to_string();
+        section.content_line_offset = lines_before_content(content, raw_content);
+        let (word_count, reading_time) = get_reading_analytics(&section.raw_content, config);
         section.word_count = Some(word_count);
         section.reading_time = Some(reading_time);
 
@@ -147,6 +153,8 @@ impl Section {
     pub fn render_markdown(
         &mut self,
         permalinks: &HashMap<String, String>,
+        wiki_link_targets: &WikiLinkTargets,
+        titles: &HashMap<String, String>,
         tera: &Tera,
         config: &Config,
         shortcode_definitions: &HashMap<String, ShortcodeDefinition>,
@@ -161,6 +169,11 @@ impl Section {
         );
         context.set_shortcode_definitions(shortcode_definitions);
         context.set_current_page_path(&self.file.relative);
+        context.set_wiki_link_targets(wiki_link_targets);
+        context.set_titles(titles);
+        if self.meta.glossary {
+            context.set_glossary(config.markdown.glossaries.get(&self.lang));
+        }
         context
             .tera_context
             .insert("section", &SerializingSection::new(self, SectionSerMode::ForMarkdown));
@@ -202,6 +215,11 @@ impl Section {
         self.file.components.is_empty()
     }
 
+    /// What links the section to its translations in `Library::translations`
+    pub fn translation_id(&self) -> PathBuf {
+        translation_id(&self.file.canonical, &self.meta.translation_key)
+    }
+
     /// Creates a vectors of asset URLs.
     fn serialize_assets(&self) -> Vec<String> {
         self.assets
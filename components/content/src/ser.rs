@@ -3,6 +3,8 @@ use std::path::Path;
 
 use serde::Serialize;
 
+use crate::authors::SerializedAuthor;
+use crate::git::GitInfo;
 use crate::library::Library;
 use crate::{Page, Section};
 use libs::tera::{Map, Value};
@@ -39,6 +41,14 @@ fn find_backlinks<'a>(relative_path: &str, library: &'a Library) -> Vec<BackLink
     backlinks
 }
 
+/// The authors of a page are resolved to their profile if they are defined in `[authors]`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum PageAuthor<'a> {
+    Name(&'a str),
+    Profile(SerializedAuthor<'a>),
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct SerializingPage<'a> {
     relative_path: &'a str,
@@ -55,7 +65,7 @@ pub struct SerializingPage<'a> {
     month: Option<u8>,
     day: Option<u8>,
     taxonomies: &'a HashMap<String, Vec<String>>,
-    authors: &'a [String],
+    authors: Vec<PageAuthor<'a>>,
     extra: &'a Map<String, Value>,
     path: &'a str,
     components: &'a [String],
@@ -65,11 +75,14 @@ pub struct SerializingPage<'a> {
     reading_time: Option<usize>,
     assets: &'a [String],
     draft: bool,
+    unlisted: bool,
+    is_fallback: bool,
     lang: &'a str,
     lower: Option<Box<SerializingPage<'a>>>,
     higher: Option<Box<SerializingPage<'a>>>,
     translations: Vec<TranslatedContent<'a>>,
     backlinks: Vec<BackLink<'a>>,
+    git: &'a Option<GitInfo>,
 }
 
 impl<'a> SerializingPage<'a> {
@@ -86,9 +99,19 @@ impl<'a> SerializingPage<'a> {
         let mut higher = None;
         let mut translations = vec![];
         let mut backlinks = vec![];
+        let mut authors: Vec<_> = page.meta.authors.iter().map(|a| PageAuthor::Name(a)).collect();
 
         if let Some(lib) = library {
-            translations = lib.find_translations(&page.file.canonical);
+            translations = lib.find_translations(&page.translation_id());
+            authors = page
+                .meta
+                .authors
+                .iter()
+                .map(|a| match lib.find_author(a, &page.lang) {
+                    Some(author) => PageAuthor::Profile(author.serialize_without_pages(lib)),
+                    None => PageAuthor::Name(a),
+                })
+                .collect();
 
             if include_siblings {
                 lower = page
@@ -120,7 +143,7 @@ impl<'a> SerializingPage<'a> {
             month,
             day,
             taxonomies: &page.meta.taxonomies,
-            authors: &page.meta.authors,
+            authors,
             path: &page.path,
             components: &page.components,
             summary: &page.summary,
@@ -129,11 +152,14 @@ impl<'a> SerializingPage<'a> {
             reading_time: page.reading_time,
             assets: &page.serialized_assets,
             draft: page.meta.draft,
+            unlisted: page.meta.unlisted,
+            is_fallback: page.is_fallback,
             lang: &page.lang,
             lower,
             higher,
             translations,
             backlinks,
+            git: &page.git,
         }
     }
 }
@@ -187,7 +213,7 @@ impl<'a> SerializingSection<'a> {
         match mode {
             SectionSerMode::ForMarkdown => {}
             SectionSerMode::MetadataOnly(lib) | SectionSerMode::Full(lib) => {
-                translations = lib.find_translations(&section.file.canonical);
+                translations = lib.find_translations(&section.translation_id());
                 subsections = section
                     .subsections
                     .iter()
@@ -4,9 +4,19 @@ use libs::unicode_segmentation::UnicodeSegmentation;
 use libs::walkdir::WalkDir;
 
 use config::Config;
+use markdown::extract_prose;
 use utils::fs::is_temp_file;
 use utils::table_of_contents::Heading;
 
+/// Content is linked to its translations through its canonical path, or through its
+/// `translation_key` if it has one
+pub fn translation_id(canonical: &Path, translation_key: &Option<String>) -> PathBuf {
+    match translation_key {
+        Some(key) => PathBuf::from(format!("translation_key:{}", key)),
+        None => canonical.to_path_buf(),
+    }
+}
+
 pub fn has_anchor(headings: &[Heading], anchor: &str) -> bool {
     for heading in headings {
         if heading.id == anchor {
@@ -58,12 +68,17 @@ pub fn find_related_assets(path: &Path, config: &Config, recursive: bool) -> Vec
     assets
 }
 
-/// Get word count and estimated reading time
-pub fn get_reading_analytics(content: &str) -> (usize, usize) {
-    // code fences "toggle" the state from non-code to code and back, so anything inbetween the
-    // first fence and the next can be ignored
-    let split = content.split("```");
-    let word_count = split.step_by(2).map(|section| section.unicode_words().count()).sum();
+/// The number of lines of a file before its content, which is what remains at the end of the
+/// file once its front matter is split off
+pub fn lines_before_content(file: &str, content: &str) -> usize {
+    file[..file.len() - content.len()].matches('\n').count()
+}
+
+/// Get word count and estimated reading time, counting the words of the prose that
+/// `zola check --spelling` checks: code, HTML, URLs and shortcode calls are left out
+pub fn get_reading_analytics(content: &str, config: &Config) -> (usize, usize) {
+    let word_count =
+        extract_prose(content, config).iter().map(|(_, text)| text.unicode_words().count()).sum();
 
     // https://help.medium.com/hc/en-us/articles/214991667-Read-time
     // 275 seems a bit too high though
@@ -222,14 +237,15 @@ mod tests {
 
     #[test]
     fn reading_analytics_empty_text() {
-        let (word_count, reading_time) = get_reading_analytics("  ");
+        let (word_count, reading_time) = get_reading_analytics("  ", &Config::default_for_test());
         assert_eq!(word_count, 0);
         assert_eq!(reading_time, 0);
     }
 
     #[test]
     fn reading_analytics_short_text() {
-        let (word_count, reading_time) = get_reading_analytics("Hello World");
+        let (word_count, reading_time) =
+            get_reading_analytics("Hello World", &Config::default_for_test());
         assert_eq!(word_count, 2);
         assert_eq!(reading_time, 1);
     }
@@ -240,22 +256,31 @@ mod tests {
         for _ in 0..1000 {
             content.push_str(" Hello world");
         }
-        let (word_count, reading_time) = get_reading_analytics(&content);
+        let (word_count, reading_time) =
+            get_reading_analytics(&content, &Config::default_for_test());
         assert_eq!(word_count, 2000);
         assert_eq!(reading_time, 10);
     }
 
     #[test]
     fn reading_analytics_no_code() {
+        let config = Config::default_for_test();
         let (word_count, reading_time) =
-            get_reading_analytics("hello world ``` code goes here ``` goodbye world");
+            get_reading_analytics("hello world ``` code goes here ``` goodbye world", &config);
         assert_eq!(word_count, 4);
         assert_eq!(reading_time, 1);
 
         let (word_count, reading_time) = get_reading_analytics(
-            "hello world ``` code goes here ``` goodbye world ``` dangling fence",
+            "hello world\n\n```\ncode goes here\n```\n\ngoodbye world\n\n```\ndangling fence",
+            &config,
         );
         assert_eq!(word_count, 4);
         assert_eq!(reading_time, 1);
+
+        let (word_count, _) = get_reading_analytics(
+            "hello `inline code` world\n\n    indented code\n\n<div>html</div>",
+            &config,
+        );
+        assert_eq!(word_count, 2);
     }
 }
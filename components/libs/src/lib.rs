@@ -16,7 +16,9 @@ pub use gh_emoji;
 pub use glob;
 pub use globset;
 pub use grass;
+pub use hypher;
 pub use image;
+pub use intl_pluralrules;
 pub use lexical_sort;
 pub use minify_html;
 pub use nom_bibtex;
@@ -34,12 +36,52 @@ pub use serde_json;
 pub use serde_yaml;
 pub use sha2;
 pub use slug;
+#[cfg(feature = "tree-sitter")]
+pub use streaming_iterator;
 pub use svg_metadata;
 pub use syntect;
 pub use tera;
 pub use termcolor;
 pub use time;
 pub use toml;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_bash;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_c;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_cpp;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_css;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_go;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_html;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_java;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_javascript;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_json;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_language;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_nix;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_python;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_ruby;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_rust;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_toml_ng;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_typescript;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_yaml;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_zig;
 pub use unic_langid;
 pub use unicode_segmentation;
 pub use url;
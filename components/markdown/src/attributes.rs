@@ -0,0 +1,181 @@
+//! The `{#id .class key=value}` attribute syntax for images, links, paragraphs, tables and
+//! fenced code blocks. Headings attributes are handled by pulldown-cmark directly.
+use libs::once_cell::sync::Lazy;
+use libs::pulldown_cmark_escape::escape_html;
+use libs::regex::Regex;
+
+/// A `name="value"` attribute of an HTML opening tag
+static HTML_ATTRIBUTE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\s([^\s="'<>/]+)="([^"]*)""#).unwrap());
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Attributes {
+    pub id: Option<String>,
+    pub classes: Vec<String>,
+    pub attrs: Vec<(String, String)>,
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_' || c == ':'
+}
+
+/// Parses the attributes at the start of `s`, returning them with the length of `s` they span.
+/// Returns `None` if `s` does not start with a valid, non-empty, list of attributes.
+pub fn parse_attributes(s: &str) -> Option<(Attributes, usize)> {
+    let mut rest = s.strip_prefix('{')?;
+    let mut attributes = Attributes::default();
+
+    loop {
+        rest = rest.trim_start();
+        let token_end = rest.find(|c: char| c.is_whitespace() || c == '}').unwrap_or(rest.len());
+        if let Some(after) = rest.strip_prefix('}') {
+            if attributes == Attributes::default() {
+                return None;
+            }
+            return Some((attributes, s.len() - after.len()));
+        } else if let Some(id) = rest[..token_end].strip_prefix('#') {
+            if id.is_empty() || !id.chars().all(is_key_char) {
+                return None;
+            }
+            attributes.id = Some(id.to_string());
+            rest = &rest[token_end..];
+        } else if let Some(class) = rest[..token_end].strip_prefix('.') {
+            if class.is_empty() || !class.chars().all(is_key_char) {
+                return None;
+            }
+            attributes.classes.push(class.to_string());
+            rest = &rest[token_end..];
+        } else {
+            let (key, value) = rest.split_once('=')?;
+            if key.is_empty() || !key.chars().all(is_key_char) {
+                return None;
+            }
+            let (value, after) = match value.chars().next()? {
+                quote @ ('"' | '\'') => {
+                    let end = value[1..].find(quote)? + 1;
+                    (&value[1..end], &value[end + 1..])
+                }
+                _ => {
+                    let end =
+                        value.find(|c: char| c.is_whitespace() || c == '}').unwrap_or(value.len());
+                    (&value[..end], &value[end..])
+                }
+            };
+            attributes.attrs.push((key.to_string(), value.to_string()));
+            rest = after;
+        }
+    }
+}
+
+/// Parses `s` if it is only a list of attributes, ignoring surrounding whitespace
+pub fn parse_only_attributes(s: &str) -> Option<Attributes> {
+    let s = s.trim();
+    match parse_attributes(s) {
+        Some((attributes, len)) if len == s.len() => Some(attributes),
+        _ => None,
+    }
+}
+
+/// Splits the attributes at the end of the info string of a fenced code block, if any
+pub fn split_fence_attributes(fence_info: &str) -> (&str, Option<Attributes>) {
+    if let Some(start) = fence_info.rfind('{') {
+        if let Some(attributes) = parse_only_attributes(&fence_info[start..]) {
+            return (fence_info[..start].trim_end(), Some(attributes));
+        }
+    }
+    (fence_info, None)
+}
+
+impl Attributes {
+    /// Adds the attributes to the first HTML tag of `html`. Classes are added to the existing
+    /// `class` attribute, other attributes replace the existing ones with the same name.
+    pub fn apply_to_tag(&self, html: &str) -> String {
+        let tag_end = html.find('>').unwrap_or(html.len());
+        let (tag, rest) = html.split_at(tag_end);
+        let (mut tag, self_closing) = match tag.strip_suffix('/') {
+            Some(t) => (t.trim_end().to_string(), true),
+            None => (tag.to_string(), false),
+        };
+
+        let classes = self.classes.join(" ");
+        let id_and_class = [("id", self.id.as_deref()), ("class", Some(classes.as_str()))];
+        let attrs = id_and_class
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, value.filter(|v| !v.is_empty())?)))
+            .chain(self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+
+        for (name, value) in attrs {
+            let mut escaped = String::new();
+            escape_html(&mut escaped, value).unwrap();
+            let existing = HTML_ATTRIBUTE_RE.captures_iter(&tag).find(|caps| &caps[1] == name);
+            match existing {
+                Some(caps) => {
+                    let existing_value = caps.get(2).unwrap();
+                    let value = if name == "class" && !existing_value.as_str().trim().is_empty() {
+                        format!("{} {}", existing_value.as_str().trim(), escaped)
+                    } else {
+                        escaped
+                    };
+                    tag.replace_range(existing_value.range(), &value);
+                }
+                None => tag.push_str(&format!(" {}=\"{}\"", name, escaped)),
+            }
+        }
+
+        if self_closing {
+            tag.push_str(" /");
+        }
+        tag.push_str(rest);
+        tag
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_attributes() {
+        let (attributes, len) =
+            parse_attributes("{#intro .wide .big loading=eager alt='a b' title=\"x=y\"} rest")
+                .unwrap();
+        assert_eq!(len, 55);
+        assert_eq!(
+            attributes,
+            Attributes {
+                id: Some("intro".to_string()),
+                classes: vec!["wide".to_string(), "big".to_string()],
+                attrs: vec![
+                    ("loading".to_string(), "eager".to_string()),
+                    ("alt".to_string(), "a b".to_string()),
+                    ("title".to_string(), "x=y".to_string()),
+                ],
+            }
+        );
+
+        for invalid in ["{}", "{ }", "{.}", "{wide}", "{.wide", "{a=\"b}", "{=b}", "not {.wide}"] {
+            assert_eq!(parse_attributes(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn can_split_fence_attributes() {
+        let (info, attributes) = split_fence_attributes("rust,linenos {.wide}");
+        assert_eq!(info, "rust,linenos");
+        assert_eq!(attributes.unwrap().classes, vec!["wide".to_string()]);
+        assert_eq!(split_fence_attributes("rust,hl_lines=1"), ("rust,hl_lines=1", None));
+    }
+
+    #[test]
+    fn can_apply_attributes_to_tag() {
+        let attributes = parse_only_attributes("{#x .wide loading=eager data-a=\"<b>\"}").unwrap();
+        assert_eq!(
+            attributes.apply_to_tag(r#"<img src="a.png" class="c" loading="lazy" />"#),
+            r#"<img src="a.png" class="c wide" loading="eager" id="x" data-a="&lt;b&gt;" />"#
+        );
+        assert_eq!(
+            attributes.apply_to_tag("<p>text</p>"),
+            r#"<p id="x" class="wide" loading="eager" data-a="&lt;b&gt;">text</p>"#
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Highlighting of terminal output: the SGR escape sequences setting colours, bold, underline etc
+//! are turned into styled spans and the other escape sequences are removed.
+use std::fmt::Write;
+
+use config::highlighting::ANSI_COLORS;
+use libs::tera::escape_html;
+
+const ESC: char = '\u{1b}';
+const BEL: char = '\u{7}';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AnsiColor {
+    /// One of the 256 colours of the palette
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl AnsiColor {
+    /// Parses the colour following `38`/`48` in a SGR sequence, consuming its parameters
+    fn parse<'a>(params: &mut impl Iterator<Item = &'a str>) -> Option<AnsiColor> {
+        let mut next = || params.next().and_then(|p| p.parse::<u8>().ok());
+        match next()? {
+            5 => Some(AnsiColor::Indexed(next()?)),
+            2 => Some(AnsiColor::Rgb(next()?, next()?, next()?)),
+            _ => None,
+        }
+    }
+
+    fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            AnsiColor::Rgb(r, g, b) => (r, g, b),
+            AnsiColor::Indexed(i) if i < 16 => {
+                let [r, g, b] = ANSI_COLORS[i as usize].1;
+                (r, g, b)
+            }
+            // The 6x6x6 colour cube
+            AnsiColor::Indexed(i) if i < 232 => {
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                let i = i - 16;
+                (level(i / 36), level((i / 6) % 6), level(i % 6))
+            }
+            // The grayscale ramp
+            AnsiColor::Indexed(i) => {
+                let v = 8 + (i - 232) * 10;
+                (v, v, v)
+            }
+        }
+    }
+
+    /// The name of the colour if it is one of the 16 basic ones, which have a class
+    fn name(self) -> Option<&'static str> {
+        match self {
+            AnsiColor::Indexed(i) if i < 16 => Some(ANSI_COLORS[i as usize].0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct AnsiStyle {
+    foreground: Option<AnsiColor>,
+    background: Option<AnsiColor>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+}
+
+impl AnsiStyle {
+    /// Applies the parameters of a SGR sequence, ignoring the ones that are not supported
+    fn apply(&mut self, params: &str) {
+        let mut params = params.split(';');
+        while let Some(param) = params.next() {
+            match param.parse::<u8>().unwrap_or(0) {
+                0 => *self = AnsiStyle::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                9 => self.strikethrough = true,
+                22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                29 => self.strikethrough = false,
+                n @ 30..=37 => self.foreground = Some(AnsiColor::Indexed(n - 30)),
+                38 => self.foreground = AnsiColor::parse(&mut params),
+                39 => self.foreground = None,
+                n @ 40..=47 => self.background = Some(AnsiColor::Indexed(n - 40)),
+                48 => self.background = AnsiColor::parse(&mut params),
+                49 => self.background = None,
+                n @ 90..=97 => self.foreground = Some(AnsiColor::Indexed(n - 90 + 8)),
+                n @ 100..=107 => self.background = Some(AnsiColor::Indexed(n - 100 + 8)),
+                _ => (),
+            }
+        }
+    }
+
+    /// The opening span of the style, using classes for what has one if `classed`
+    /// and inline styles otherwise
+    fn opening_span(&self, classed: bool) -> String {
+        let mut classes = Vec::new();
+        let mut styles = String::new();
+
+        let colors = [("", "color", self.foreground), ("bg-", "background-color", self.background)];
+        for (class_prefix, property, color) in colors {
+            let Some(color) = color else { continue };
+            match color.name() {
+                Some(name) if classed => classes.push(format!("z-ansi-{}{}", class_prefix, name)),
+                _ => {
+                    let (r, g, b) = color.rgb();
+                    write!(styles, "{}:#{:02x}{:02x}{:02x};", property, r, g, b).unwrap();
+                }
+            }
+        }
+
+        let flags = [
+            (self.bold, "bold", "font-weight:bold;"),
+            (self.dim, "dim", "opacity:0.7;"),
+            (self.italic, "italic", "font-style:italic;"),
+        ];
+        for (enabled, class, style) in flags {
+            if enabled {
+                if classed {
+                    classes.push(format!("z-ansi-{}", class));
+                } else {
+                    styles.push_str(style);
+                }
+            }
+        }
+        let decorations = [(self.underline, "underline"), (self.strikethrough, "strikethrough")];
+        if classed {
+            for (enabled, class) in decorations {
+                if enabled {
+                    classes.push(format!("z-ansi-{}", class));
+                }
+            }
+        } else if self.underline || self.strikethrough {
+            let values: Vec<_> = decorations
+                .into_iter()
+                .filter(|(enabled, _)| *enabled)
+                .map(|(_, d)| if d == "strikethrough" { "line-through" } else { d })
+                .collect();
+            write!(styles, "text-decoration:{};", values.join(" ")).unwrap();
+        }
+
+        let mut span = String::from("<span");
+        if !classes.is_empty() {
+            write!(span, " class=\"{}\"", classes.join(" ")).unwrap();
+        }
+        if !styles.is_empty() {
+            write!(span, " style=\"{}\"", styles).unwrap();
+        }
+        span.push('>');
+        span
+    }
+}
+
+pub(crate) struct AnsiHighlighter {
+    /// Whether to use the `z-ansi-*` classes rather than inline styles
+    classed: bool,
+    /// Whether to only remove the escape sequences
+    plain: bool,
+    /// The style at the current point, which carries over to the next lines
+    style: AnsiStyle,
+}
+
+impl AnsiHighlighter {
+    pub fn new(classed: bool, plain: bool) -> Self {
+        Self { classed, plain, style: AnsiStyle::default() }
+    }
+
+    pub fn is_plain(&self) -> bool {
+        self.plain
+    }
+
+    /// Renders a line, with each span closed at the end of the line and opened again on the next
+    pub fn highlight_line(&mut self, line: &str) -> String {
+        let mut out = String::with_capacity(line.len());
+        let mut in_span = false;
+        let mut text = String::new();
+        let (classed, plain) = (self.classed, self.plain);
+
+        let flush = |out: &mut String, text: &mut String, style: &AnsiStyle, in_span: &mut bool| {
+            if text.is_empty() {
+                return;
+            }
+            if !*in_span && !plain && *style != AnsiStyle::default() {
+                out.push_str(&style.opening_span(classed));
+                *in_span = true;
+            }
+            out.push_str(&escape_html(text));
+            text.clear();
+        };
+
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != ESC {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                // Control Sequence Introducer: parameters then a final byte
+                Some('[') => {
+                    let mut params = String::new();
+                    let mut final_byte = None;
+                    for c in chars.by_ref() {
+                        if ('\u{40}'..='\u{7e}').contains(&c) {
+                            final_byte = Some(c);
+                            break;
+                        }
+                        params.push(c);
+                    }
+                    if final_byte == Some('m') {
+                        let mut style = self.style.clone();
+                        style.apply(&params);
+                        if style != self.style {
+                            flush(&mut out, &mut text, &self.style, &mut in_span);
+                            if in_span {
+                                out.push_str("</span>");
+                                in_span = false;
+                            }
+                            self.style = style;
+                        }
+                    }
+                }
+                // Operating System Command, e.g. hyperlinks or window titles, until BEL or ST
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == BEL || (c == ESC && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        flush(&mut out, &mut text, &self.style, &mut in_span);
+        if in_span {
+            out.push_str("</span>");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn highlight(classed: bool, plain: bool, code: &str) -> String {
+        let mut highlighter = AnsiHighlighter::new(classed, plain);
+        code.split_inclusive('\n').map(|line| highlighter.highlight_line(line)).collect()
+    }
+
+    #[test]
+    fn can_use_classes_for_basic_styles() {
+        let code = "\u{1b}[1;31merror\u{1b}[0m: a <b>\n\u{1b}[4;92mok\u{1b}[24m!\u{1b}[m\n";
+        assert_eq!(
+            highlight(true, false, code),
+            "<span class=\"z-ansi-red z-ansi-bold\">error</span>: a &lt;b&gt;\n\
+             <span class=\"z-ansi-bright-green z-ansi-underline\">ok</span>\
+             <span class=\"z-ansi-bright-green\">!</span>\n"
+        );
+    }
+
+    #[test]
+    fn can_use_inline_styles() {
+        let code = "\u{1b}[38;5;196;48;2;0;0;255;4;9mx\u{1b}[0m\n";
+        assert_eq!(
+            highlight(false, false, code),
+            "<span style=\"color:#ff0000;background-color:#0000ff;text-decoration:underline line-through;\">x</span>\n"
+        );
+        // Colours without a class are inlined in classed mode
+        assert_eq!(
+            highlight(true, false, "\u{1b}[38;5;240;1mx"),
+            "<span class=\"z-ansi-bold\" style=\"color:#585858;\">x</span>"
+        );
+    }
+
+    #[test]
+    fn carries_styles_over_lines() {
+        assert_eq!(
+            highlight(true, false, "\u{1b}[33ma\nb\u{1b}[0m\nc\n"),
+            "<span class=\"z-ansi-yellow\">a\n</span><span class=\"z-ansi-yellow\">b</span>\nc\n"
+        );
+    }
+
+    #[test]
+    fn removes_other_escape_sequences() {
+        let code =
+            "\u{1b}[2K\u{1b}]8;;https://example.com\u{7}link\u{1b}]8;;\u{1b}\\ \u{1b}[31mred\n";
+        assert_eq!(highlight(true, true, code), "link red\n");
+    }
+}
@@ -26,6 +26,8 @@ pub struct FenceSettings<'a> {
     pub hide_lines: Vec<RangeInclusive<usize>>,
     pub name: Option<&'a str>,
     pub enable_copy: bool,
+    /// Whether the lines start with the `+`, `-` or ` ` markers of a diff
+    pub diff: bool,
 }
 
 impl<'a> FenceSettings<'a> {
@@ -38,10 +40,13 @@ impl<'a> FenceSettings<'a> {
             hide_lines: Vec::new(),
             name: None,
             enable_copy: false,
+            diff: false,
         };
 
         for token in FenceIter::new(fence_info) {
             match token {
+                // `diff` after the language marks the changes of code in that language
+                FenceToken::Language("diff") if me.language.is_some() => me.diff = true,
                 FenceToken::Language(lang) => me.language = Some(lang),
                 FenceToken::EnableLineNumbers => me.line_numbers = true,
                 FenceToken::InitialLineNumber(l) => me.line_number_start = l,
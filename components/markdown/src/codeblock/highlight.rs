@@ -1,6 +1,6 @@
 use std::fmt::Write;
 
-use config::highlighting::{SyntaxAndTheme, CLASS_STYLE};
+use config::highlighting::{HighlightSource, SyntaxAndTheme, CLASS_STYLE};
 use libs::syntect::easy::HighlightLines;
 use libs::syntect::highlighting::{Color, Theme};
 use libs::syntect::html::{
@@ -11,8 +11,12 @@ use libs::syntect::parsing::{
 };
 use libs::tera::escape_html;
 
+use crate::codeblock::ansi::AnsiHighlighter;
+#[cfg(feature = "tree-sitter")]
+use crate::codeblock::treesitter::TreeSitterHighlighter;
+
 /// Not public, but from syntect::html
-fn write_css_color(s: &mut String, c: Color) {
+pub(crate) fn write_css_color(s: &mut String, c: Color) {
     if c.a != 0xFF {
         write!(s, "#{:02x}{:02x}{:02x}{:02x}", c.r, c.g, c.b, c.a).unwrap();
     } else {
@@ -21,7 +25,7 @@ fn write_css_color(s: &mut String, c: Color) {
 }
 
 /// Not public, but from syntect::html
-fn scope_to_classes(s: &mut String, scope: Scope, style: ClassStyle) {
+pub(crate) fn scope_to_classes(s: &mut String, scope: Scope, style: ClassStyle) {
     let repo = SCOPE_REPO.lock().unwrap();
     for i in 0..(scope.len()) {
         let atom = scope.atom_at(i as usize);
@@ -130,11 +134,20 @@ pub(crate) enum SyntaxHighlighter<'config> {
     Classed(ClassHighlighter<'config>),
     /// We might not want highlighting but we want line numbers or to hide some lines
     NoHighlight,
+    /// Terminal output, with the theme if the styles are inlined
+    Ansi(AnsiHighlighter, Option<&'config Theme>),
+    /// Highlights the whole code at once, with the theme if the styles are inlined
+    #[cfg(feature = "tree-sitter")]
+    TreeSitter(TreeSitterHighlighter<'config>),
 }
 
 impl<'config> SyntaxHighlighter<'config> {
     pub fn new(highlight_code: bool, s: SyntaxAndTheme<'config>) -> Self {
-        if highlight_code {
+        if s.source == HighlightSource::Ansi {
+            // The escape sequences are removed even without highlighting
+            let highlighter = AnsiHighlighter::new(s.theme.is_none(), !highlight_code);
+            SyntaxHighlighter::Ansi(highlighter, s.theme.filter(|_| highlight_code))
+        } else if highlight_code {
             if let Some(theme) = s.theme {
                 SyntaxHighlighter::Inlined(InlineHighlighter::new(s.syntax, s.syntax_set, theme))
             } else {
@@ -152,26 +165,46 @@ impl<'config> SyntaxHighlighter<'config> {
             Inlined(h) => h.highlight_line(line),
             Classed(h) => h.highlight_line(line),
             NoHighlight => escape_html(line),
+            Ansi(h, _) => h.highlight_line(line),
+            #[cfg(feature = "tree-sitter")]
+            TreeSitter(h) => h.highlight(line).concat(),
         }
     }
 
-    /// Inlined needs to set the background/foreground colour on <pre>
-    pub fn pre_style(&self) -> Option<String> {
+    /// Highlights the lines of a code block, returning the HTML of each one
+    pub fn highlight_lines<'a>(&mut self, lines: impl Iterator<Item = &'a str>) -> Vec<String> {
+        match self {
+            // Tree-sitter needs the whole code to parse it
+            #[cfg(feature = "tree-sitter")]
+            SyntaxHighlighter::TreeSitter(h) => h.highlight(&lines.collect::<String>()),
+            _ => lines.map(|line| self.highlight_line(line)).collect(),
+        }
+    }
+
+    /// The theme used for the inline styles, if there is one
+    fn inline_theme(&self) -> Option<&'config Theme> {
         use SyntaxHighlighter::*;
 
         match self {
             Classed(_) | NoHighlight => None,
-            Inlined(h) => {
-                let mut styles = String::from("background-color:");
-                write_css_color(&mut styles, h.theme.settings.background.unwrap_or(Color::WHITE));
-                styles.push_str(";color:");
-                write_css_color(&mut styles, h.theme.settings.foreground.unwrap_or(Color::BLACK));
-                styles.push(';');
-                Some(styles)
-            }
+            Inlined(h) => Some(h.theme),
+            Ansi(_, theme) => *theme,
+            #[cfg(feature = "tree-sitter")]
+            TreeSitter(h) => h.theme,
         }
     }
 
+    /// Inlined needs to set the background/foreground colour on <pre>
+    pub fn pre_style(&self) -> Option<String> {
+        let theme = self.inline_theme()?;
+        let mut styles = String::from("background-color:");
+        write_css_color(&mut styles, theme.settings.background.unwrap_or(Color::WHITE));
+        styles.push_str(";color:");
+        write_css_color(&mut styles, theme.settings.foreground.unwrap_or(Color::BLACK));
+        styles.push(';');
+        Some(styles)
+    }
+
     /// Classed needs to set a class on the pre
     pub fn pre_class(&self) -> Option<String> {
         use SyntaxHighlighter::*;
@@ -184,26 +217,37 @@ impl<'config> SyntaxHighlighter<'config> {
                     unreachable!()
                 }
             }
-            Inlined(_) | NoHighlight => None,
+            Ansi(h, None) if !h.is_plain() => {
+                if let ClassStyle::SpacedPrefixed { prefix } = CLASS_STYLE {
+                    Some(format!("{}code", prefix))
+                } else {
+                    unreachable!()
+                }
+            }
+            #[cfg(feature = "tree-sitter")]
+            TreeSitter(h) if h.theme.is_none() => {
+                if let ClassStyle::SpacedPrefixed { prefix } = CLASS_STYLE {
+                    Some(format!("{}code", prefix))
+                } else {
+                    unreachable!()
+                }
+            }
+            Inlined(_) | NoHighlight | Ansi(..) => None,
+            #[cfg(feature = "tree-sitter")]
+            TreeSitter(_) => None,
         }
     }
 
     /// Inlined needs to set the background/foreground colour
     pub fn mark_style(&self) -> Option<String> {
-        use SyntaxHighlighter::*;
-
-        match self {
-            Classed(_) | NoHighlight => None,
-            Inlined(h) => {
-                let mut styles = String::from("background-color:");
-                write_css_color(
-                    &mut styles,
-                    h.theme.settings.line_highlight.unwrap_or(Color { r: 255, g: 255, b: 0, a: 0 }),
-                );
-                styles.push(';');
-                Some(styles)
-            }
-        }
+        let theme = self.inline_theme()?;
+        let mut styles = String::from("background-color:");
+        write_css_color(
+            &mut styles,
+            theme.settings.line_highlight.unwrap_or(Color { r: 255, g: 255, b: 0, a: 0 }),
+        );
+        styles.push(';');
+        Some(styles)
     }
 }
 
@@ -1,14 +1,19 @@
+mod ansi;
 mod fence;
 mod highlight;
+#[cfg(feature = "tree-sitter")]
+mod treesitter;
 
+use std::borrow::Cow;
 use std::ops::RangeInclusive;
 
 use errors::{bail, Result};
+use libs::regex::Regex;
 use libs::syntect::util::LinesWithEndings;
 
 use crate::codeblock::highlight::SyntaxHighlighter;
-use config::highlighting::{resolve_syntax_and_theme, HighlightSource};
-use config::Config;
+use config::highlighting::{resolve_syntax_and_theme, HighlightSource, SyntaxAndTheme};
+use config::{Config, Highlighter};
 pub(crate) use fence::FenceSettings;
 
 fn opening_html(
@@ -18,6 +23,7 @@ fn opening_html(
     pre_class: Option<String>,
     line_numbers: bool,
     enable_copy: bool,
+    diff: bool,
 ) -> String {
     let mut html = String::from("<pre");
     if line_numbers {
@@ -26,6 +32,9 @@ fn opening_html(
     if enable_copy {
         html.push_str(" data-copy");
     }
+    if diff {
+        html.push_str(" data-diff");
+    }
     let mut classes = String::new();
 
     if let Some(lang) = language {
@@ -78,6 +87,126 @@ fn opening_html(
     html
 }
 
+/// A line of a `diff` code block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffLine {
+    Added,
+    Removed,
+    Context,
+}
+
+impl DiffLine {
+    /// Splits the `+`, `-` or ` ` marker from the line. Lines without a marker are context.
+    fn split(line: &str) -> (DiffLine, &str) {
+        if let Some(rest) = line.strip_prefix('+') {
+            (DiffLine::Added, rest)
+        } else if let Some(rest) = line.strip_prefix('-') {
+            (DiffLine::Removed, rest)
+        } else {
+            (DiffLine::Context, line.strip_prefix(' ').unwrap_or(line))
+        }
+    }
+
+    /// Wraps the highlighted line in `<ins>` or `<del>`. The marker is only shown through the
+    /// `data-marker` attribute, e.g. with CSS, so that it is not part of the copied text.
+    fn wrap(self, highlighted: &str) -> String {
+        let (tag, class, marker) = match self {
+            DiffLine::Added => ("ins", "diff-add", "+"),
+            DiffLine::Removed => ("del", "diff-del", "-"),
+            DiffLine::Context => ("span", "diff-context", " "),
+        };
+        format!(
+            "<{tag} class=\"{class}\"><span class=\"diff-marker\" data-marker=\"{marker}\"></span>{highlighted}</{tag}>"
+        )
+    }
+}
+
+/// The id of a callout in a code block and of its annotation, the number of the code block in the
+/// page keeping them unique
+pub fn callout_ids(block: usize, number: usize) -> (String, String) {
+    (format!("code-callout-{}-{}", block, number), format!("code-annotation-{}-{}", block, number))
+}
+
+/// Warns or errors, depending on the config, if the language to highlight was not found
+fn check_language_found(
+    syntax_and_theme: &SyntaxAndTheme,
+    language: Option<&str>,
+    config: &Config,
+    path: Option<&str>,
+) -> Result<()> {
+    if syntax_and_theme.source == HighlightSource::NotFound && config.markdown.highlight_code {
+        let lang = language.unwrap();
+        let msg = if let Some(p) = path {
+            format!("Highlight language {} not found in {}", lang, p)
+        } else {
+            format!("Highlight language {} not found", lang)
+        };
+        if config.markdown.error_on_missing_highlight {
+            bail!(msg);
+        } else {
+            eprintln!("Warning: {}", msg);
+        }
+    }
+    Ok(())
+}
+
+/// Creates the highlighter of the code in `language`, using tree-sitter if it is selected and
+/// has a grammar for it
+fn new_highlighter<'config>(
+    language: Option<&str>,
+    config: &'config Config,
+    path: Option<&str>,
+) -> Result<SyntaxHighlighter<'config>> {
+    let highlight_code = config.markdown.highlight_code;
+    let syntax_and_theme = resolve_syntax_and_theme(language, config);
+
+    if highlight_code && config.markdown.highlighter == Highlighter::TreeSitter {
+        #[cfg(feature = "tree-sitter")]
+        if let Some(highlighter) =
+            language.and_then(|l| treesitter::TreeSitterHighlighter::new(l, syntax_and_theme.theme))
+        {
+            return Ok(SyntaxHighlighter::TreeSitter(highlighter));
+        }
+        #[cfg(not(feature = "tree-sitter"))]
+        bail!(
+            "The `tree-sitter` highlighter was selected but Zola was built without the `tree-sitter` feature"
+        );
+    }
+
+    check_language_found(&syntax_and_theme, language, config, path)?;
+    Ok(SyntaxHighlighter::new(highlight_code, syntax_and_theme))
+}
+
+/// Highlights inline code in the given language like a code block, returning the `<code>` element
+pub fn highlight_inline_code(
+    code: &str,
+    language: &str,
+    config: &Config,
+    path: Option<&str>,
+) -> Result<String> {
+    let mut highlighter = new_highlighter(Some(language), config, path)?;
+
+    let mut html = format!("<code class=\"language-{}", language);
+    if let Some(class) = highlighter.pre_class() {
+        html.push(' ');
+        html.push_str(&class);
+    }
+    html.push_str("\" data-lang=\"");
+    html.push_str(language);
+    html.push('"');
+    if let Some(style) = highlighter.pre_style() {
+        html.push_str(" style=\"");
+        html.push_str(&style);
+        html.push('"');
+    }
+    html.push('>');
+    // Inline code is on a single line but the highlighting works line by line
+    let highlighted = highlighter.highlight_line(&format!("{}\n", code));
+    html.push_str(&highlighted.replace('\n', ""));
+    html.push_str("</code>");
+    Ok(html)
+}
+
 pub struct CodeBlock<'config> {
     highlighter: SyntaxHighlighter<'config>,
     // fence options
@@ -85,6 +214,12 @@ pub struct CodeBlock<'config> {
     line_number_start: usize,
     highlight_lines: Vec<RangeInclusive<usize>>,
     hide_lines: Vec<RangeInclusive<usize>>,
+    diff: bool,
+    callouts_regex: Option<&'config Regex>,
+    /// The number of the code block in the page
+    block: usize,
+    /// The numbers of the callouts found, in order
+    callouts: Vec<usize>,
 }
 
 impl<'config> CodeBlock<'config> {
@@ -93,22 +228,10 @@ impl<'config> CodeBlock<'config> {
         config: &'config Config,
         // path to the current file if there is one, to point where the error is
         path: Option<&'config str>,
+        // the number of the code block in the page, for the ids of its callouts
+        block: usize,
     ) -> Result<(Self, String)> {
-        let syntax_and_theme = resolve_syntax_and_theme(fence.language, config);
-        if syntax_and_theme.source == HighlightSource::NotFound && config.markdown.highlight_code {
-            let lang = fence.language.unwrap();
-            let msg = if let Some(p) = path {
-                format!("Highlight language {} not found in {}", lang, p)
-            } else {
-                format!("Highlight language {} not found", lang)
-            };
-            if config.markdown.error_on_missing_highlight {
-                bail!(msg);
-            } else {
-                eprintln!("Warning: {}", msg);
-            }
-        }
-        let highlighter = SyntaxHighlighter::new(config.markdown.highlight_code, syntax_and_theme);
+        let highlighter = new_highlighter(fence.language, config, path)?;
 
         let html_start = opening_html(
             fence.language,
@@ -117,6 +240,7 @@ impl<'config> CodeBlock<'config> {
             highlighter.pre_class(),
             fence.line_numbers,
             fence.enable_copy,
+            fence.diff,
         );
         Ok((
             Self {
@@ -125,11 +249,62 @@ impl<'config> CodeBlock<'config> {
                 line_number_start: fence.line_number_start,
                 highlight_lines: fence.highlight_lines,
                 hide_lines: fence.hide_lines,
+                diff: fence.diff,
+                callouts_regex: config.markdown.code_callouts_regex.as_ref(),
+                block,
+                callouts: Vec::new(),
             },
             html_start,
         ))
     }
 
+    /// The numbers of the callouts found while highlighting, in order
+    pub fn callouts(&self) -> &[usize] {
+        &self.callouts
+    }
+
+    /// Removes the callout markers from the line, returning it with the HTML of the callouts
+    fn extract_callouts<'a>(&mut self, line: &'a str) -> (Cow<'a, str>, String) {
+        let Some(regex) = self.callouts_regex else {
+            return (Cow::Borrowed(line), String::new());
+        };
+
+        let mut stripped = String::new();
+        let mut html = String::new();
+        let mut last = 0;
+        for caps in regex.captures_iter(line) {
+            let Some(number) = caps.get(1).and_then(|n| n.as_str().parse().ok()) else {
+                continue;
+            };
+            let marker = caps.get(0).unwrap();
+            stripped.push_str(&line[last..marker.start()]);
+            last = marker.end();
+
+            let (callout_id, annotation_id) = callout_ids(self.block, number);
+            // Only the first callout with a given number can be linked back to
+            let id = if self.callouts.contains(&number) {
+                String::new()
+            } else {
+                format!(" id=\"{}\"", callout_id)
+            };
+            html.push_str(&format!(
+                "<a class=\"code-callout\"{} href=\"#{}\">{}</a>",
+                id, annotation_id, number
+            ));
+            self.callouts.push(number);
+        }
+        if html.is_empty() {
+            return (Cow::Borrowed(line), html);
+        }
+
+        stripped.push_str(&line[last..]);
+        // Drop the spaces that were before the marker at the end of the line
+        let line_ending = &stripped[stripped.trim_end().len()..];
+        let line_ending = if line_ending.contains('\n') { "\n" } else { "" };
+        let stripped = format!("{}{}", stripped.trim_end(), line_ending);
+        (Cow::Owned(stripped), html)
+    }
+
     pub fn highlight(&mut self, content: &str) -> String {
         let mut buffer = String::new();
         let mark_style = self.highlighter.mark_style();
@@ -138,6 +313,9 @@ impl<'config> CodeBlock<'config> {
             buffer.push_str("<table><tbody>");
         }
 
+        // First remove what is not code from the lines that are shown, as some highlighters
+        // need the whole code at once
+        let mut lines = Vec::new();
         // syntect leaking here in this file
         for (i, line) in LinesWithEndings::from(content).enumerate() {
             let one_indexed = i + 1;
@@ -153,6 +331,22 @@ impl<'config> CodeBlock<'config> {
                 continue;
             }
 
+            let (diff_line, line) = if self.diff {
+                let (diff_line, line) = DiffLine::split(line);
+                (Some(diff_line), line)
+            } else {
+                (None, line)
+            };
+            let (line, callouts) = self.extract_callouts(line);
+            lines.push((i, diff_line, line, callouts));
+        }
+        let highlighted_lines =
+            self.highlighter.highlight_lines(lines.iter().map(|(_, _, line, _)| line.as_ref()));
+
+        for ((i, diff_line, _, callouts), mut highlighted_line) in
+            lines.into_iter().zip(highlighted_lines)
+        {
+            let one_indexed = i + 1;
             // Next is it supposed to be higlighted?
             let mut is_higlighted = false;
             for range in &self.highlight_lines {
@@ -185,8 +379,15 @@ impl<'config> CodeBlock<'config> {
                 buffer.push_str("</td><td>");
             }
 
-            let highlighted_line = self.highlighter.highlight_line(line);
-            maybe_mark(&mut buffer, &highlighted_line);
+            if !callouts.is_empty() {
+                // Before the end of line, which is the only newline left after highlighting
+                let end = highlighted_line.rfind('\n').unwrap_or(highlighted_line.len());
+                highlighted_line.insert_str(end, &callouts);
+            }
+            match diff_line {
+                Some(diff_line) => maybe_mark(&mut buffer, &diff_line.wrap(&highlighted_line)),
+                None => maybe_mark(&mut buffer, &highlighted_line),
+            }
 
             if self.line_numbers {
                 buffer.push_str("</td></tr>");
@@ -0,0 +1,352 @@
+//! Highlighting with the tree-sitter grammars bundled with the `tree-sitter` feature.
+//! The captures of their highlight queries are mapped to the TextMate scopes used by syntect,
+//! so that the same themes and classes work with both.
+use std::ops::Range;
+
+use libs::once_cell::sync::OnceCell;
+use libs::streaming_iterator::StreamingIterator;
+use libs::syntect::highlighting::{FontStyle, Highlighter, Theme};
+use libs::syntect::parsing::Scope;
+use libs::tera::escape_html;
+use libs::tree_sitter::{Language, Parser, Query, QueryCursor};
+use libs::tree_sitter_language::LanguageFn;
+use libs::{
+    tree_sitter_bash as bash, tree_sitter_c as c, tree_sitter_cpp as cpp, tree_sitter_css as css,
+    tree_sitter_go as go, tree_sitter_html as html, tree_sitter_java as java,
+    tree_sitter_javascript as javascript, tree_sitter_json as json, tree_sitter_nix as nix,
+    tree_sitter_python as python, tree_sitter_ruby as ruby, tree_sitter_rust as rust,
+    tree_sitter_toml_ng as toml, tree_sitter_typescript as typescript, tree_sitter_yaml as yaml,
+    tree_sitter_zig as zig,
+};
+
+use crate::codeblock::highlight::{scope_to_classes, write_css_color};
+use config::highlighting::CLASS_STYLE;
+
+struct Grammar {
+    /// The fence languages using that grammar
+    names: &'static [&'static str],
+    language: LanguageFn,
+    /// The highlight queries, the patterns of the first ones taking precedence
+    queries: &'static [&'static str],
+    query: OnceCell<Query>,
+}
+
+impl Grammar {
+    const fn new(
+        names: &'static [&'static str],
+        language: LanguageFn,
+        queries: &'static [&'static str],
+    ) -> Self {
+        Grammar { names, language, queries, query: OnceCell::new() }
+    }
+
+    /// The queries are only compiled for the languages used
+    fn query(&self) -> &Query {
+        self.query.get_or_init(|| {
+            Query::new(&Language::new(self.language), &self.queries.concat())
+                .expect("the bundled highlight queries are valid")
+        })
+    }
+}
+
+static GRAMMARS: [Grammar; 19] = [
+    Grammar::new(&["bash", "sh", "shell", "zsh"], bash::LANGUAGE, &[bash::HIGHLIGHT_QUERY]),
+    Grammar::new(&["c", "h"], c::LANGUAGE, &[c::HIGHLIGHT_QUERY]),
+    Grammar::new(
+        &["cpp", "c++", "cc", "cxx", "hpp"],
+        cpp::LANGUAGE,
+        &[cpp::HIGHLIGHT_QUERY, c::HIGHLIGHT_QUERY],
+    ),
+    Grammar::new(&["css"], css::LANGUAGE, &[css::HIGHLIGHTS_QUERY]),
+    Grammar::new(&["go", "golang"], go::LANGUAGE, &[go::HIGHLIGHTS_QUERY]),
+    Grammar::new(&["html", "htm"], html::LANGUAGE, &[html::HIGHLIGHTS_QUERY]),
+    Grammar::new(&["java"], java::LANGUAGE, &[java::HIGHLIGHTS_QUERY]),
+    Grammar::new(
+        &["javascript", "js", "mjs", "cjs"],
+        javascript::LANGUAGE,
+        &[javascript::HIGHLIGHT_QUERY],
+    ),
+    Grammar::new(
+        &["jsx"],
+        javascript::LANGUAGE,
+        &[javascript::JSX_HIGHLIGHT_QUERY, javascript::HIGHLIGHT_QUERY],
+    ),
+    Grammar::new(&["json"], json::LANGUAGE, &[json::HIGHLIGHTS_QUERY]),
+    Grammar::new(&["nix"], nix::LANGUAGE, &[nix::HIGHLIGHTS_QUERY]),
+    Grammar::new(&["python", "py"], python::LANGUAGE, &[python::HIGHLIGHTS_QUERY]),
+    Grammar::new(&["ruby", "rb"], ruby::LANGUAGE, &[ruby::HIGHLIGHTS_QUERY]),
+    Grammar::new(&["rust", "rs"], rust::LANGUAGE, &[rust::HIGHLIGHTS_QUERY]),
+    Grammar::new(&["toml"], toml::LANGUAGE, &[toml::HIGHLIGHTS_QUERY]),
+    Grammar::new(
+        &["typescript", "ts"],
+        typescript::LANGUAGE_TYPESCRIPT,
+        &[typescript::HIGHLIGHTS_QUERY, javascript::HIGHLIGHT_QUERY],
+    ),
+    Grammar::new(
+        &["tsx"],
+        typescript::LANGUAGE_TSX,
+        &[
+            typescript::HIGHLIGHTS_QUERY,
+            javascript::JSX_HIGHLIGHT_QUERY,
+            javascript::HIGHLIGHT_QUERY,
+        ],
+    ),
+    Grammar::new(&["yaml", "yml"], yaml::LANGUAGE, &[yaml::HIGHLIGHTS_QUERY]),
+    Grammar::new(&["zig"], zig::LANGUAGE, &[zig::HIGHLIGHTS_QUERY]),
+];
+
+/// The TextMate scopes of the tree-sitter captures, by prefix. The rest of the capture name is
+/// appended to the scope, `keyword.control` being `keyword.control` and `function.method`
+/// `entity.name.function.method` for example.
+const CAPTURE_SCOPES: &[(&str, &str)] = &[
+    ("attribute", "entity.other.attribute-name"),
+    ("boolean", "constant.language.boolean"),
+    ("character", "constant.character"),
+    ("comment", "comment"),
+    ("constant.builtin", "constant.language"),
+    ("constant", "constant"),
+    ("constructor", "entity.name.function.constructor"),
+    ("embedded", "meta.embedded"),
+    ("escape", "constant.character.escape"),
+    ("function.builtin", "support.function"),
+    ("function.macro", "support.macro"),
+    ("function", "entity.name.function"),
+    ("keyword", "keyword"),
+    ("label", "entity.name.label"),
+    ("module", "entity.name.namespace"),
+    ("namespace", "entity.name.namespace"),
+    ("number", "constant.numeric"),
+    ("operator", "keyword.operator"),
+    ("property", "variable.other.member"),
+    ("punctuation", "punctuation"),
+    ("string", "string"),
+    ("tag", "entity.name.tag"),
+    ("type.builtin", "storage.type"),
+    ("type", "entity.name.type"),
+    ("variable.builtin", "variable.language"),
+    ("variable", "variable"),
+];
+
+/// The scope of a capture, if it is to be highlighted
+fn capture_scope(capture: &str) -> Option<Scope> {
+    if capture.starts_with('_')
+        || capture.starts_with("local")
+        || capture.starts_with("injection")
+        || ["none", "spell", "nospell", "conceal"].contains(&capture)
+    {
+        return None;
+    }
+
+    let scope = CAPTURE_SCOPES
+        .iter()
+        .find_map(|(prefix, scope)| {
+            let rest = capture.strip_prefix(prefix)?;
+            (rest.is_empty() || rest.starts_with('.')).then(|| format!("{}{}", scope, rest))
+        })
+        .unwrap_or_else(|| capture.to_string());
+    Scope::new(&scope).ok()
+}
+
+/// Writes the highlighted code line by line, closing the open spans at the end of each line and
+/// opening them again on the next one
+#[derive(Default)]
+struct LineWriter<'a> {
+    lines: Vec<String>,
+    current: String,
+    /// The opening tags of the open spans, with where they end
+    open: Vec<(&'a str, usize)>,
+    /// Whether the open spans still need to be opened on the current line
+    reopen: bool,
+}
+
+impl<'a> LineWriter<'a> {
+    fn text(&mut self, text: &str) {
+        for part in text.split_inclusive('\n') {
+            if self.reopen {
+                for (tag, _) in &self.open {
+                    self.current.push_str(tag);
+                }
+                self.reopen = false;
+            }
+            self.current.push_str(&escape_html(part));
+            if part.ends_with('\n') {
+                for _ in &self.open {
+                    self.current.push_str("</span>");
+                }
+                self.lines.push(std::mem::take(&mut self.current));
+                self.reopen = true;
+            }
+        }
+    }
+
+    fn open(&mut self, tag: &'a str, end: usize) {
+        if !self.reopen {
+            self.current.push_str(tag);
+        }
+        self.open.push((tag, end));
+    }
+
+    /// Closes the spans ending before `pos`, writing the code up to their end
+    fn close_until(&mut self, pos: usize, code: &str, written: &mut usize) {
+        while let Some(&(_, end)) = self.open.last() {
+            if end > pos {
+                break;
+            }
+            self.text(&code[*written..end]);
+            *written = end;
+            self.open.pop();
+            if !self.reopen {
+                self.current.push_str("</span>");
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<String> {
+        if !self.current.is_empty() {
+            self.lines.push(self.current);
+        }
+        self.lines
+    }
+}
+
+pub(crate) struct TreeSitterHighlighter<'config> {
+    grammar: &'static Grammar,
+    /// The opening span of each capture of the query, if it is highlighted
+    tags: Vec<Option<String>>,
+    pub(crate) theme: Option<&'config Theme>,
+}
+
+impl<'config> TreeSitterHighlighter<'config> {
+    /// Returns `None` if there is no bundled grammar for that language
+    pub fn new(language: &str, theme: Option<&'config Theme>) -> Option<Self> {
+        let grammar = GRAMMARS.iter().find(|g| g.names.contains(&language))?;
+        let highlighter = theme.map(Highlighter::new);
+        let default_style = highlighter.as_ref().map(|h| h.get_default());
+
+        let tags = grammar
+            .query()
+            .capture_names()
+            .iter()
+            .map(|name| {
+                let scope = capture_scope(name)?;
+                match (&highlighter, default_style) {
+                    (Some(highlighter), Some(default_style)) => {
+                        let style = highlighter.style_for_stack(&[scope]);
+                        let mut css = String::new();
+                        if style.foreground != default_style.foreground {
+                            css.push_str("color:");
+                            write_css_color(&mut css, style.foreground);
+                            css.push(';');
+                        }
+                        if style.background != default_style.background {
+                            css.push_str("background-color:");
+                            write_css_color(&mut css, style.background);
+                            css.push(';');
+                        }
+                        if style.font_style.contains(FontStyle::BOLD) {
+                            css.push_str("font-weight:bold;");
+                        }
+                        if style.font_style.contains(FontStyle::ITALIC) {
+                            css.push_str("font-style:italic;");
+                        }
+                        if style.font_style.contains(FontStyle::UNDERLINE) {
+                            css.push_str("text-decoration:underline;");
+                        }
+                        (!css.is_empty()).then(|| format!("<span style=\"{}\">", css))
+                    }
+                    _ => {
+                        let mut tag = String::from("<span class=\"");
+                        scope_to_classes(&mut tag, scope, CLASS_STYLE);
+                        tag.push_str("\">");
+                        Some(tag)
+                    }
+                }
+            })
+            .collect();
+
+        Some(Self { grammar, tags, theme })
+    }
+
+    /// Highlights the whole code, returning the HTML of each of its lines
+    pub fn highlight(&self, code: &str) -> Vec<String> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&Language::new(self.grammar.language))
+            .expect("the bundled grammars are compatible");
+        let tree = parser.parse(code, None).expect("parsing only fails when cancelled");
+
+        let mut cursor = QueryCursor::new();
+        let mut captures = cursor.captures(self.grammar.query(), tree.root_node(), code.as_bytes());
+        let mut writer = LineWriter::default();
+        let mut written = 0;
+        let mut previous: Option<Range<usize>> = None;
+
+        while let Some((query_match, index)) = captures.next() {
+            let capture = query_match.captures[*index];
+            let mut range = capture.node.byte_range();
+            // A node captured by several patterns gets the capture of the first one
+            if range.is_empty() || previous.as_ref() == Some(&range) {
+                continue;
+            }
+            let Some(tag) = &self.tags[capture.index as usize] else { continue };
+
+            writer.close_until(range.start, code, &mut written);
+            if range.start < written {
+                continue;
+            }
+            if let Some(&(_, end)) = writer.open.last() {
+                range.end = range.end.min(end);
+            }
+            writer.text(&code[written..range.start]);
+            written = range.start;
+            writer.open(tag, range.end);
+            previous = Some(range);
+        }
+        writer.close_until(code.len(), code, &mut written);
+        writer.text(&code[written..]);
+        writer.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config::highlighting::THEME_SET;
+
+    #[test]
+    fn can_compile_all_queries() {
+        for grammar in &GRAMMARS {
+            assert!(!grammar.query().capture_names().is_empty(), "{:?}", grammar.names);
+        }
+    }
+
+    #[test]
+    fn can_map_captures_to_scopes() {
+        assert_eq!(
+            capture_scope("function.method"),
+            Scope::new("entity.name.function.method").ok()
+        );
+        assert_eq!(capture_scope("keyword"), Scope::new("keyword").ok());
+        assert_eq!(capture_scope("functional"), Scope::new("functional").ok());
+        assert_eq!(capture_scope("_name"), None);
+    }
+
+    #[test]
+    fn can_highlight_with_classes_line_by_line() {
+        let highlighter = TreeSitterHighlighter::new("tsx", None).unwrap();
+        let lines = highlighter.highlight("/* a\nb */ const x = <div>{1}</div>;\n");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "<span class=\"z-comment\">&#x2F;* a\n</span>");
+        assert!(lines[1].starts_with("<span class=\"z-comment\">b *&#x2F;</span> "));
+        assert!(lines[1].contains("<span class=\"z-keyword\">const</span>"));
+    }
+
+    #[test]
+    fn can_highlight_with_theme() {
+        let theme = &THEME_SET.themes["base16-ocean-dark"];
+        let highlighter = TreeSitterHighlighter::new("rust", Some(theme)).unwrap();
+        let lines = highlighter.highlight("let x = 1;");
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("<span style=\"color:#b48ead;\">let</span>"));
+    }
+}
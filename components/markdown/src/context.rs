@@ -1,10 +1,11 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-use config::Config;
+use config::{Config, Glossary};
 use libs::tera::{Context, Tera};
+use utils::site::WikiLinkTargets;
 use utils::templates::ShortcodeDefinition;
-use utils::types::InsertAnchor;
+use utils::types::{AutoSummary, InsertAnchor};
 
 /// All the information from the zola site that is needed to render HTML from markdown
 #[derive(Debug)]
@@ -18,6 +19,14 @@ pub struct RenderContext<'a> {
     pub insert_anchor: InsertAnchor,
     pub lang: &'a str,
     pub shortcode_definitions: Cow<'a, HashMap<String, ShortcodeDefinition>>,
+    /// How to generate a summary if there is no `<!-- more -->` marker
+    pub auto_summary: AutoSummary,
+    /// What `[[Title]]` links can point to, if `wiki_links` is enabled
+    pub wiki_link_targets: Cow<'a, WikiLinkTargets>,
+    /// The titles of the pages and sections by relative path, to fill internal links without text
+    pub titles: Cow<'a, HashMap<String, String>>,
+    /// The glossary terms to link on first use, unless the page opted out
+    pub glossary: Option<&'a Glossary>,
 }
 
 impl<'a> RenderContext<'a> {
@@ -43,6 +52,10 @@ impl<'a> RenderContext<'a> {
             config,
             lang,
             shortcode_definitions: Cow::Owned(HashMap::new()),
+            auto_summary: AutoSummary::None,
+            wiki_link_targets: Cow::Owned(WikiLinkTargets::default()),
+            titles: Cow::Owned(HashMap::new()),
+            glossary: None,
         }
     }
 
@@ -52,6 +65,26 @@ impl<'a> RenderContext<'a> {
         self.shortcode_definitions = Cow::Borrowed(def);
     }
 
+    /// Same as above
+    pub fn set_auto_summary(&mut self, auto_summary: AutoSummary) {
+        self.auto_summary = auto_summary;
+    }
+
+    /// Same as above
+    pub fn set_wiki_link_targets(&mut self, targets: &'a WikiLinkTargets) {
+        self.wiki_link_targets = Cow::Borrowed(targets);
+    }
+
+    /// Same as above
+    pub fn set_titles(&mut self, titles: &'a HashMap<String, String>) {
+        self.titles = Cow::Borrowed(titles);
+    }
+
+    /// Same as above
+    pub fn set_glossary(&mut self, glossary: Option<&'a Glossary>) {
+        self.glossary = glossary;
+    }
+
     /// Same as above
     pub fn set_current_page_path(&mut self, path: &'a str) {
         self.current_page_path = Some(path);
@@ -71,6 +104,10 @@ impl<'a> RenderContext<'a> {
             config,
             lang: &config.default_language,
             shortcode_definitions: Cow::Owned(HashMap::new()),
+            auto_summary: AutoSummary::None,
+            wiki_link_targets: Cow::Owned(WikiLinkTargets::default()),
+            titles: Cow::Owned(HashMap::new()),
+            glossary: None,
         }
     }
 }
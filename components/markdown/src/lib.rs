@@ -1,7 +1,10 @@
+mod attributes;
 mod codeblock;
 mod context;
+mod lint;
 mod markdown;
 mod shortcode;
+mod typography;
 
 use shortcode::{extract_shortcodes, insert_md_shortcodes};
 
@@ -10,6 +13,7 @@ use errors::Result;
 use crate::markdown::markdown_to_html;
 pub use crate::markdown::Rendered;
 pub use context::RenderContext;
+pub use lint::{extract_prose, lint_markdown, LintIssue};
 
 pub fn render_content(content: &str, context: &RenderContext) -> Result<markdown::Rendered> {
     // avoid parsing the content if needed
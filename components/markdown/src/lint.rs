@@ -0,0 +1,295 @@
+//! The checks of `zola check` that look at the markdown itself: the content lint rules and the
+//! extraction of the prose to spellcheck. The content is parsed as written, before shortcodes
+//! are rendered.
+use std::collections::HashMap;
+
+use config::{Config, LintRule};
+use libs::once_cell::sync::Lazy;
+use libs::pulldown_cmark::{Event, LinkType, Parser, Tag, TagEnd};
+use libs::regex::Regex;
+use utils::slugs::slugify_anchors;
+
+use crate::markdown::parser_options;
+
+static BARE_URL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:https?|ftp)://[^\s<>]+[^\s<>.,;:!?)'\x22]").unwrap());
+/// Shortcode calls, whose arguments often contain URLs
+static SHORTCODE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)\{\{.*?\}\}|\{%.*?%\}").unwrap());
+
+/// The index of the line of each offset of the content, starting at 1
+fn line_finder(content: &str) -> impl Fn(usize) -> usize {
+    let line_starts: Vec<usize> =
+        std::iter::once(0).chain(content.match_indices('\n').map(|(i, _)| i + 1)).collect();
+    move |offset| line_starts.partition_point(|&start| start <= offset)
+}
+
+/// Something a lint rule found, at a line of the content starting at 1
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintIssue {
+    pub rule: LintRule,
+    pub line: usize,
+    pub message: String,
+}
+
+struct OpenLink {
+    destination: String,
+    line: usize,
+    text: String,
+    has_content: bool,
+}
+
+struct OpenHeading {
+    id: Option<String>,
+    line: usize,
+    text: String,
+}
+
+/// Runs the markdown lint rules on the content, whatever their level
+pub fn lint_markdown(content: &str, config: &Config) -> Vec<LintIssue> {
+    let line_of = line_finder(content);
+
+    let mut issues = Vec::new();
+    let mut issue = |rule, line, message| issues.push(LintIssue { rule, line, message });
+
+    let mut previous_level: Option<usize> = None;
+    let mut h1_line: Option<usize> = None;
+    // anchor -> the line of the heading using it
+    let mut anchors: HashMap<String, usize> = HashMap::new();
+    let mut heading: Option<OpenHeading> = None;
+    let mut link: Option<OpenLink> = None;
+    let mut image: Option<(String, usize, String)> = None;
+    let mut in_code_block = false;
+
+    for (event, range) in Parser::new_ext(content, parser_options(config)).into_offset_iter() {
+        let line = line_of(range.start);
+        if let Some(link) = link.as_mut() {
+            if !matches!(event, Event::End(TagEnd::Link)) {
+                link.has_content = true;
+            }
+        }
+
+        match event {
+            Event::Start(Tag::Heading { level, id, .. }) => {
+                let level = level as usize;
+                if let Some(previous) = previous_level.filter(|p| level > p + 1) {
+                    issue(
+                        LintRule::SkippedHeadingLevel,
+                        line,
+                        format!("The heading skips from h{} to h{}", previous, level),
+                    );
+                }
+                previous_level = Some(level);
+                if level == 1 {
+                    match h1_line {
+                        Some(first) => issue(
+                            LintRule::MultipleH1,
+                            line,
+                            format!("There is already an h1 heading on line {}", first),
+                        ),
+                        None => h1_line = Some(line),
+                    }
+                }
+                heading = Some(OpenHeading {
+                    id: id.map(|id| id.to_string()),
+                    line,
+                    text: String::new(),
+                });
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                let Some(heading) = heading.take() else { continue };
+                let anchor = heading.id.unwrap_or_else(|| {
+                    slugify_anchors(heading.text.trim(), config.slugify.anchors)
+                });
+                if let Some(first) = anchors.get(&anchor) {
+                    issue(
+                        LintRule::DuplicateAnchor,
+                        heading.line,
+                        format!(
+                            "The anchor `{}` is already used by the heading on line {}",
+                            anchor, first
+                        ),
+                    );
+                } else {
+                    anchors.insert(anchor, heading.line);
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                link = Some(OpenLink {
+                    destination: dest_url.to_string(),
+                    line,
+                    text: String::new(),
+                    has_content: false,
+                });
+            }
+            Event::End(TagEnd::Link) => {
+                let Some(link) = link.take() else { continue };
+                if link.destination.trim().is_empty() {
+                    issue(
+                        LintRule::EmptyLink,
+                        link.line,
+                        format!("The link `{}` has no destination", link.text.trim()),
+                    );
+                } else if !link.has_content {
+                    issue(
+                        LintRule::EmptyLink,
+                        link.line,
+                        format!("The link to `{}` has no text", link.destination),
+                    );
+                }
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                image = Some((dest_url.to_string(), line, String::new()));
+            }
+            Event::End(TagEnd::Image) => {
+                let Some((destination, line, alt)) = image.take() else { continue };
+                if alt.trim().is_empty() {
+                    issue(
+                        LintRule::MissingAltText,
+                        line,
+                        format!("The image `{}` has no alt text", destination),
+                    );
+                }
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(ref text) | Event::Code(ref text) => {
+                for buffer in [
+                    heading.as_mut().map(|h| &mut h.text),
+                    link.as_mut().map(|l| &mut l.text),
+                    image.as_mut().map(|i| &mut i.2),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    buffer.push_str(text);
+                }
+
+                let is_prose = matches!(event, Event::Text(_)) && !in_code_block;
+                if is_prose && link.is_none() && image.is_none() {
+                    let text = SHORTCODE_RE.replace_all(text, "");
+                    for url in BARE_URL_RE.find_iter(&text) {
+                        issue(
+                            LintRule::BareUrl,
+                            line,
+                            format!("The URL `{}` is not a link", url.as_str()),
+                        );
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    issues
+}
+
+/// The text of the content with the line it is on, without code, HTML, URLs or shortcode calls.
+/// This is what `word_count` counts and what `zola check --spelling` checks.
+pub fn extract_prose(content: &str, config: &Config) -> Vec<(usize, String)> {
+    let line_of = line_finder(content);
+    let mut prose: Vec<(usize, String)> = Vec::new();
+    let mut in_code_block = false;
+    let mut in_autolink = false;
+    // The parser can split a word in several text events, e.g. at an apostrophe
+    let mut previous_is_text = false;
+
+    for (event, range) in Parser::new_ext(content, parser_options(config)).into_offset_iter() {
+        let is_text = matches!(event, Event::Text(_)) && !in_code_block && !in_autolink;
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Start(Tag::Link { link_type: LinkType::Autolink | LinkType::Email, .. }) => {
+                in_autolink = true;
+            }
+            Event::End(TagEnd::Link) => in_autolink = false,
+            Event::Text(text) if is_text => match prose.last_mut() {
+                Some((_, previous)) if previous_is_text => previous.push_str(&text),
+                _ => prose.push((line_of(range.start), text.into_string())),
+            },
+            _ => (),
+        }
+        previous_is_text = is_text;
+    }
+
+    prose
+        .into_iter()
+        .map(|(line, text)| {
+            let text = SHORTCODE_RE.replace_all(&text, " ");
+            (line, BARE_URL_RE.replace_all(&text, " ").into_owned())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(content: &str) -> Vec<(LintRule, usize)> {
+        let config = Config::default_for_test();
+        lint_markdown(content, &config).into_iter().map(|i| (i.rule, i.line)).collect()
+    }
+
+    #[test]
+    fn can_lint_headings() {
+        let content = "## Intro\n\n#### Deep\n\n# Title\n\n# Other {#intro}\n\n## Title\n";
+        assert_eq!(
+            lint(content),
+            vec![
+                (LintRule::SkippedHeadingLevel, 3),
+                (LintRule::MultipleH1, 7),
+                (LintRule::DuplicateAnchor, 7),
+                (LintRule::DuplicateAnchor, 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_lint_links_and_images() {
+        let content = "A [link]() and [](https://example.com).\n\n![](a.png) ![a cat](cat.png)\n\n[![logo](logo.png)](/)\n";
+        assert_eq!(
+            lint(content),
+            vec![(LintRule::EmptyLink, 1), (LintRule::EmptyLink, 1), (LintRule::MissingAltText, 3)]
+        );
+    }
+
+    #[test]
+    fn can_lint_bare_urls() {
+        let content = "See https://example.com/a.\n\n<https://ok.com> [ok](https://ok.com) `https://ok.com`\n\n```\nhttps://ok.com\n```\n\n{{ youtube(url=\"https://ok.com\") }}\n";
+        let config = Config::default_for_test();
+        let issues = lint_markdown(content, &config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 1);
+        assert_eq!(issues[0].message, "The URL `https://example.com/a` is not a link");
+    }
+
+    #[test]
+    fn can_extract_prose() {
+        let content = "Some *text* with `code`\n\n```\nnot prose\n```\n\n<b>html</b> <https://a.com> see https://b.com {{ sc(a=\"b\") }}\n";
+        let config = Config::default_for_test();
+        let prose: Vec<_> = extract_prose(content, &config)
+            .into_iter()
+            .map(|(line, text)| (line, text.trim().to_string()))
+            .filter(|(_, text)| !text.is_empty())
+            .collect();
+        assert_eq!(
+            prose,
+            vec![
+                (1, "Some".to_string()),
+                (1, "text".to_string()),
+                (1, "with".to_string()),
+                (7, "html".to_string()),
+                (7, "see".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_prose_skips_all_code_and_html() {
+        let content = "Before\n\n~~~\nfn foo_bar() {}\n~~~\n\n    let indented = 1;\n\n<script>\nconst notProse = 1;\n</script>\n\n<div\n  class=\"multi-line\">\n</div>\n\nIt's after\n";
+        let config = Config::default_for_test();
+        assert_eq!(
+            extract_prose(content, &config),
+            vec![(1, "Before".to_string()), (17, "It's after".to_string())]
+        );
+    }
+}
@@ -1,7 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 
 use crate::markdown::cmark::CowStr;
+use config::{Config, Glossary};
 use errors::bail;
 use libs::gh_emoji::Replacer as EmojiReplacer;
 use libs::once_cell::sync::Lazy;
@@ -17,15 +18,22 @@ use libs::regex::{Regex, RegexBuilder};
 use utils::site::resolve_internal_link;
 use utils::slugs::slugify_anchors;
 use utils::table_of_contents::{make_table_of_contents, Heading};
-use utils::types::InsertAnchor;
+use utils::types::{AutoSummary, InsertAnchor};
 
 use self::cmark::{Event, LinkType, Options, Parser, Tag, TagEnd};
-use crate::codeblock::{CodeBlock, FenceSettings};
+use crate::attributes::{
+    parse_attributes, parse_only_attributes, split_fence_attributes, Attributes,
+};
+use crate::codeblock::{callout_ids, highlight_inline_code, CodeBlock, FenceSettings};
 use crate::shortcode::{Shortcode, SHORTCODE_PLACEHOLDER};
+use crate::typography::Typography;
 
 const CONTINUE_READING: &str = "<span id=\"continue-reading\"></span>";
 const SUMMARY_CUTOFF_TEMPLATE: &str = "summary-cutoff.html";
 const ANCHOR_LINK_TEMPLATE: &str = "anchor-link.html";
+const SIDENOTE_TEMPLATE: &str = "sidenote.html";
+/// Stands for the text of a link to a heading of the current page until the headings are known
+const EMPTY_LINK_TEXT: &str = "<!-- empty link text -->";
 static EMOJI_REPLACER: Lazy<EmojiReplacer> = Lazy::new(EmojiReplacer::new);
 
 /// Set as a regex to help match some extra cases. This way, spaces and case don't matter.
@@ -41,6 +49,9 @@ static FOOTNOTES_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"<sup class="footnote-reference"><a href=\s*.*?>\s*.*?</a></sup>"#).unwrap()
 });
 
+/// The language hint following inline code, like `{:rust}`
+static INLINE_CODE_LANG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\{:([\w+#.-]+)\}").unwrap());
+
 /// Although there exists [a list of registered URI schemes][uri-schemes], a link may use arbitrary,
 /// private schemes. This regex checks if the given string starts with something that just looks
 /// like a scheme, i.e., a case-insensitive identifier followed by a colon.
@@ -91,6 +102,36 @@ pub struct Rendered {
     pub external_links: Vec<String>,
 }
 
+/// What to fill an internal link without text with
+enum EmptyLinkText {
+    /// The title of the page or section linked to
+    Title(String),
+    /// The text of the heading of the current page with that id
+    Heading(String),
+    /// The URL of a target without title, as there is nothing else to fill the link with
+    Url(String),
+}
+
+/// Warns about an internal link without text that could not be filled with a title or a heading
+fn warn_unfilled_link(url: &str, context: &RenderContext) {
+    console::warn(&format!(
+        "The link to `{}` in {} has no text and no title to fill it with, it is filled with its URL",
+        url,
+        context.current_page_path.unwrap_or("unknown"),
+    ));
+}
+
+/// The ordered list right after a code block with callouts, whose items annotate them in order
+struct CalloutAnnotations {
+    /// The number of the code block in the page
+    block: usize,
+    callouts: Vec<usize>,
+    /// The number of the current item
+    number: usize,
+    /// How many lists nested in the items we are in
+    depth: usize,
+}
+
 /// Tracks a heading in a slice of pulldown-cmark events
 #[derive(Debug)]
 struct HeadingRef {
@@ -162,11 +203,32 @@ fn fix_link(
         return Ok(link.to_string());
     }
 
+    // `[[#Some heading]]` links to a heading of the current page
+    let local_wiki_anchor;
+    let link = match (link_type, link.strip_prefix('#')) {
+        (LinkType::WikiLink { .. }, Some(heading)) => {
+            local_wiki_anchor =
+                format!("#{}", slugify_anchors(heading.trim(), context.config.slugify.anchors));
+            &local_wiki_anchor
+        }
+        _ => link,
+    };
+
     // A few situations here:
+    // - it could be a wiki link (`[[Title]]`) to a page or section
     // - it could be a relative link (starting with `@/`)
     // - it could be a link to a co-located asset
     // - it could be a normal link
-    let result = if link.starts_with("@/") {
+    let result = if matches!(link_type, LinkType::WikiLink { .. }) && !link.starts_with('#') {
+        let resolved = context
+            .wiki_link_targets
+            .resolve(link, context.lang, &context.permalinks, context.config.slugify.anchors)
+            .with_context(|| {
+                format!("Broken wiki link in {}", context.current_page_path.unwrap_or("unknown"))
+            })?;
+        internal_links.push((resolved.md_path, resolved.anchor));
+        resolved.permalink
+    } else if link.starts_with("@/") {
         match resolve_internal_link(link, &context.permalinks) {
             Ok(resolved) => {
                 internal_links.push((resolved.md_path, resolved.anchor));
@@ -209,6 +271,64 @@ fn fix_link(
     Ok(result)
 }
 
+/// Links or wraps in `<abbr>` the first use in `text` of the glossary terms that are not in
+/// `used_terms` yet
+fn insert_glossary_terms<'a>(
+    text: CowStr<'a>,
+    glossary: &Glossary,
+    context: &RenderContext,
+    used_terms: &mut HashSet<usize>,
+    internal_links: &mut Vec<(String, Option<String>)>,
+    external_links: &mut Vec<String>,
+) -> Result<Vec<Event<'a>>> {
+    if glossary.find(&text, |i| used_terms.contains(&i)).is_none() {
+        return Ok(vec![Event::Text(text)]);
+    }
+
+    let mut events = vec![];
+    let mut rest: &str = &text;
+    while let Some((range, idx)) = glossary.find(rest, |i| used_terms.contains(&i)) {
+        used_terms.insert(idx);
+        let term = &glossary.terms[idx];
+        if range.start > 0 {
+            events.push(Event::Text(rest[..range.start].to_string().into()));
+        }
+
+        let mut start = String::new();
+        let mut end = String::new();
+        if let Some(ref link) = term.link {
+            let link = fix_link(LinkType::Inline, link, context, internal_links, external_links)?;
+            start.push_str("<a class=\"glossary-term\" href=\"");
+            cmark_escape::escape_href(&mut start, &link)?;
+            start.push_str("\">");
+            end.push_str("</a>");
+        }
+        if let Some(ref title) = term.title {
+            start.push_str("<abbr title=\"");
+            escape_html(&mut start, title)?;
+            start.push_str("\">");
+            end.insert_str(0, "</abbr>");
+        }
+        events.push(Event::Html(start.into()));
+        events.push(Event::Text(rest[range.clone()].to_string().into()));
+        events.push(Event::Html(end.into()));
+
+        rest = &rest[range.end..];
+    }
+    if !rest.is_empty() {
+        events.push(Event::Text(rest.to_string().into()));
+    }
+
+    Ok(events)
+}
+
+/// Renders `events` to HTML and adds the attributes to their first tag
+fn render_with_attributes(events: &[Event], attributes: &Attributes) -> String {
+    let mut html = String::new();
+    cmark::html::push_html(&mut html, events.iter().cloned());
+    attributes.apply_to_tag(&html)
+}
+
 /// get only text in a slice of events
 fn get_text(parser_slice: &[Event]) -> String {
     let mut title = String::new();
@@ -246,6 +366,84 @@ fn get_heading_refs(events: &[Event]) -> Vec<HeadingRef> {
     heading_refs
 }
 
+/// The reference to a footnote in the text, `count` being the number of this reference to it
+fn footnote_reference(name: &str, count: usize, number: usize) -> Event<'static> {
+    Event::Html(format!(r##"<sup class="footnote-reference" id="fr-{name}-{count}"><a href="#fn-{name}">{number}</a></sup>"##).into())
+}
+
+/// A footnote listed at the bottom of the page: its body, without the definition tags, and
+/// how many references it has
+struct BottomFootnote<'a> {
+    name: CowStr<'a>,
+    number: usize,
+    count: usize,
+    body: Vec<Event<'a>>,
+}
+
+/// The list of the footnotes at the bottom of the page, each with links back to its references.
+/// `with_numbers` sets the number of each item, for lists that skip some numbers.
+fn footnotes_list<'a>(footnotes: Vec<BottomFootnote<'a>>, with_numbers: bool) -> Vec<Event<'a>> {
+    let mut events =
+        vec![Event::Html("<section class=\"footnotes\">\n<ol class=\"footnotes-list\">\n".into())];
+    for BottomFootnote { name, number, count, mut body } in footnotes {
+        // Backrefs are included in the final paragraph of the footnote, if it's normal text.
+        // For example, this DOM can be produced:
+        //
+        // Markdown:
+        //
+        //     five [^feet].
+        //
+        //     [^feet]:
+        //         A foot is defined, in this case, as 0.3048 m.
+        //
+        //         Historically, the foot has not been defined this way, corresponding to many
+        //         subtly different units depending on the location.
+        //
+        // HTML:
+        //
+        //     <p>five <sup class="footnote-reference" id="fr-feet-1"><a href="#fn-feet">1</a></sup>.</p>
+        //
+        //     <ol class="footnotes-list">
+        //     <li id="fn-feet">
+        //     <p>A foot is defined, in this case, as 0.3048 m.</p>
+        //     <p>Historically, the foot has not been defined this way, corresponding to many
+        //     subtly different units depending on the location. <a href="#fr-feet-1">↩</a></p>
+        //     </li>
+        //     </ol>
+        //
+        // This is mostly a visual hack, so that footnotes use less vertical space.
+        //
+        // If there is no final paragraph, such as a tabular, list, or image footnote, it gets
+        // pushed after the last tag instead.
+        let mut backrefs =
+            String::with_capacity(name.len() + (r##" <a href="#fr--1">↩</a>"##.len() * count));
+        for usage in 1..=count {
+            if usage == 1 {
+                write!(&mut backrefs, r##" <a href="#fr-{name}-{usage}">↩</a>"##).unwrap();
+            } else {
+                write!(&mut backrefs, r##" <a href="#fr-{name}-{usage}">↩{usage}</a>"##).unwrap();
+            }
+        }
+        match body.last() {
+            Some(Event::End(TagEnd::Paragraph)) => {
+                body.insert(body.len() - 1, Event::Html(backrefs.into()))
+            }
+            _ => body.push(Event::Html(backrefs.into())),
+        }
+
+        let item = if with_numbers {
+            format!(r##"<li id="fn-{name}" value="{number}">"##)
+        } else {
+            format!(r##"<li id="fn-{name}">"##)
+        };
+        events.push(Event::Html(item.into()));
+        events.extend(body);
+        events.push(Event::Html("</li>\n".into()));
+    }
+    events.push(Event::Html("</ol>\n</section>\n".into()));
+    events
+}
+
 fn convert_footnotes_to_github_style(old_events: &mut Vec<Event>) {
     let events = std::mem::take(old_events);
     // step 1: We need to extract footnotes from the event stream and tweak footnote references
@@ -278,7 +476,7 @@ fn convert_footnotes_to_github_style(old_events: &mut Vec<Event>) {
                 // nr is a number of references to this footnote
                 let (n, nr) = footnote_numbers.entry(name.clone()).or_insert((n, 0usize));
                 *nr += 1;
-                let reference = Event::Html(format!(r##"<sup class="footnote-reference" id="fr-{name}-{nr}"><a href="#fn-{name}">{n}</a></sup>"##).into());
+                let reference = footnote_reference(&name, *nr, *n);
 
                 if footnote_bodies_stack.is_empty() {
                     // we are in the main text, just output the reference
@@ -296,8 +494,7 @@ fn convert_footnotes_to_github_style(old_events: &mut Vec<Event>) {
             }
             _ => Some(event),
         }
-    }
-    );
+    });
 
     old_events.extend(filtered_events);
 
@@ -305,9 +502,6 @@ fn convert_footnotes_to_github_style(old_events: &mut Vec<Event>) {
         return;
     }
 
-    old_events
-        .push(Event::Html("<section class=\"footnotes\">\n<ol class=\"footnotes-list\">\n".into()));
-
     // Step 2: retain only footnotes which was actually referenced
     footnotes.retain(|f| match f.first() {
         Some(Event::Start(Tag::FootnoteDefinition(name))) => {
@@ -324,79 +518,302 @@ fn convert_footnotes_to_github_style(old_events: &mut Vec<Event>) {
         _ => unreachable!(),
     });
 
-    // Step 4: Add backreferences to footnotes
-    let footnotes = footnotes.into_iter().flat_map(|fl| {
-        // To write backrefs, the name needs kept until the end of the footnote definition.
-        let mut name = CowStr::from("");
-        // Backrefs are included in the final paragraph of the footnote, if it's normal text.
-        // For example, this DOM can be produced:
-        //
-        // Markdown:
-        //
-        //     five [^feet].
-        //
-        //     [^feet]:
-        //         A foot is defined, in this case, as 0.3048 m.
-        //
-        //         Historically, the foot has not been defined this way, corresponding to many
-        //         subtly different units depending on the location.
-        //
-        // HTML:
-        //
-        //     <p>five <sup class="footnote-reference" id="fr-feet-1"><a href="#fn-feet">1</a></sup>.</p>
-        //
-        //     <ol class="footnotes-list">
-        //     <li id="fn-feet">
-        //     <p>A foot is defined, in this case, as 0.3048 m.</p>
-        //     <p>Historically, the foot has not been defined this way, corresponding to many
-        //     subtly different units depending on the location. <a href="#fr-feet-1">↩</a></p>
-        //     </li>
-        //     </ol>
-        //
-        // This is mostly a visual hack, so that footnotes use less vertical space.
-        //
-        // If there is no final paragraph, such as a tabular, list, or image footnote, it gets
-        // pushed after the last tag instead.
-        let mut has_written_backrefs = false;
-        let fl_len = fl.len();
-        let footnote_numbers = &footnote_numbers;
-        fl.into_iter().enumerate().map(move |(i, f)| match f {
-            Event::Start(Tag::FootnoteDefinition(current_name)) => {
-                name = current_name;
-                has_written_backrefs = false;
-                Event::Html(format!(r##"<li id="fn-{name}">"##).into())
+    // Step 4: List them with backreferences, without their definition tags
+    let footnotes = footnotes
+        .into_iter()
+        .map(|mut body| {
+            body.pop();
+            let Event::Start(Tag::FootnoteDefinition(name)) = body.remove(0) else {
+                unreachable!("footnotes start with their definition")
+            };
+            let (number, count) = footnote_numbers[&name];
+            BottomFootnote { name, number, count, body }
+        })
+        .collect();
+    old_events.extend(footnotes_list(footnotes, false));
+}
+
+/// What a footnote contains that a sidenote cannot: sidenotes are inline in a paragraph so
+/// they can only hold paragraphs and inline content
+fn sidenote_blocker(event: &Event) -> Option<&'static str> {
+    match event {
+        Event::Start(Tag::List(_)) => Some("a list"),
+        Event::Start(Tag::CodeBlock(_)) => Some("a code block"),
+        Event::Start(Tag::BlockQuote(_)) => Some("a blockquote"),
+        Event::Start(Tag::Table(_)) => Some("a table"),
+        Event::Start(Tag::Heading { .. }) => Some("a heading"),
+        Event::Start(Tag::HtmlBlock) => Some("an HTML block"),
+        Event::Start(Tag::DefinitionList) => Some("a definition list"),
+        Event::Rule => Some("a horizontal rule"),
+        Event::FootnoteReference(_) => Some("a footnote reference"),
+        _ => None,
+    }
+}
+
+/// Renders the events of a footnote definition as inline HTML for a sidenote: paragraphs
+/// are unwrapped and separated by line breaks.
+fn render_sidenote_body(definition: &[Event]) -> String {
+    let mut first_paragraph = true;
+    let events = definition.iter().filter_map(|event| match event {
+        Event::Start(Tag::Paragraph) if first_paragraph => {
+            first_paragraph = false;
+            None
+        }
+        Event::Start(Tag::Paragraph) => Some(Event::Html("<br>\n".into())),
+        Event::End(TagEnd::Paragraph) => None,
+        e => Some(e.clone()),
+    });
+
+    let mut body = String::new();
+    cmark::html::push_html(&mut body, events);
+    body.truncate(body.trim_end().len());
+    body
+}
+
+/// Turns the footnote references into sidenotes, except for the footnotes with block content
+/// which are listed at the bottom of the page, sharing the same numbering
+struct Sidenotes<'a, 'c> {
+    context: &'c RenderContext<'c>,
+    definitions: HashMap<CowStr<'a>, Vec<Event<'a>>>,
+    /// The footnotes that cannot be sidenotes, with what prevents it
+    blocked: &'c HashMap<String, &'static str>,
+    /// name -> (number, how many references so far)
+    numbers: HashMap<CowStr<'a>, (usize, usize)>,
+    /// The footnotes rendered at the bottom, in the order of their first reference
+    bottom: Vec<CowStr<'a>>,
+}
+
+impl<'a> Sidenotes<'a, '_> {
+    /// Replaces the footnote references of `events`. Inside a footnote at the bottom of the page,
+    /// references point to the bottom as well.
+    fn convert(&mut self, events: Vec<Event<'a>>, in_bottom: bool) -> Result<Vec<Event<'a>>> {
+        let mut converted = Vec::with_capacity(events.len());
+        for event in events {
+            let Event::FootnoteReference(name) = event else {
+                converted.push(event);
+                continue;
+            };
+            let n = self.numbers.len() + 1;
+            // count is the number of references to this footnote so far
+            let (number, count) = *self
+                .numbers
+                .entry(name.clone())
+                .and_modify(|(_, count)| *count += 1)
+                .or_insert((n, 1));
+
+            if in_bottom || self.blocked.contains_key(name.as_ref()) {
+                if !self.bottom.contains(&name) {
+                    self.bottom.push(name.clone());
+                }
+                converted.push(footnote_reference(&name, count, number));
+                continue;
             }
-            Event::End(TagEnd::FootnoteDefinition) | Event::End(TagEnd::Paragraph)
-                if !has_written_backrefs && i >= fl_len - 2 =>
-            {
-                let usage_count = footnote_numbers.get(&name).unwrap().1;
-                let mut end = String::with_capacity(
-                    name.len() + (r##" <a href="#fr--1">↩</a></li>"##.len() * usage_count),
-                );
-                for usage in 1..=usage_count {
-                    if usage == 1 {
-                        write!(&mut end, r##" <a href="#fr-{name}-{usage}">↩</a>"##).unwrap();
-                    } else {
-                        write!(&mut end, r##" <a href="#fr-{name}-{usage}">↩{usage}</a>"##)
-                            .unwrap();
+
+            let body =
+                self.definitions.get(&name).map(|d| render_sidenote_body(d)).unwrap_or_default();
+            let mut c = tera::Context::new();
+            c.insert("name", &*name);
+            c.insert("number", &number);
+            c.insert("count", &count);
+            c.insert("body", &body);
+            c.insert("lang", &self.context.lang);
+            let sidenote =
+                utils::templates::render_template(SIDENOTE_TEMPLATE, &self.context.tera, c, &None)
+                    .context("Failed to render sidenote template")?;
+            converted.push(Event::Html(sidenote.into()));
+        }
+        Ok(converted)
+    }
+
+    /// The list of the footnotes that could not be sidenotes, with links back to their references
+    fn bottom_footnotes(&mut self) -> Result<Vec<Event<'a>>> {
+        // Footnotes can reference other footnotes, adding them to the list as we go
+        let mut bodies = Vec::new();
+        let mut i = 0;
+        while let Some(name) = self.bottom.get(i).cloned() {
+            let definition = self.definitions.get(&name).cloned().unwrap_or_default();
+            bodies.push((name, self.convert(definition, true)?));
+            i += 1;
+        }
+        if bodies.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let footnotes = bodies
+            .into_iter()
+            .map(|(name, body)| {
+                let (number, count) = self.numbers[&name];
+                BottomFootnote { name, number, count, body }
+            })
+            .collect();
+        Ok(footnotes_list(footnotes, true))
+    }
+}
+
+/// Replaces each footnote reference with its footnote, rendered next to it with the
+/// `sidenote.html` template. Footnotes are numbered in the order they are first referenced.
+/// The footnotes in `blocked` cannot be inline and are listed at the bottom of the page instead.
+fn convert_footnotes_to_sidenotes(
+    old_events: &mut Vec<Event>,
+    context: &RenderContext,
+    blocked: &HashMap<String, &'static str>,
+) -> Result<()> {
+    // step 1: extract the footnote definitions from the event stream, using a stack as
+    // footnotes can be defined inside footnotes
+    let mut definitions: HashMap<CowStr, Vec<Event>> = HashMap::new();
+    let mut definitions_stack = Vec::new();
+    let mut events = Vec::with_capacity(old_events.len());
+    for event in std::mem::take(old_events) {
+        match event {
+            Event::Start(Tag::FootnoteDefinition(name)) => definitions_stack.push((name, vec![])),
+            Event::End(TagEnd::FootnoteDefinition) => {
+                // unwrap will never fail, because Tag::FootnoteDefinition always comes before
+                // TagEnd::FootnoteDefinition
+                let (name, definition) = definitions_stack.pop().unwrap();
+                definitions.entry(name).or_insert(definition);
+            }
+            _ => match definitions_stack.last_mut() {
+                Some((_, definition)) => definition.push(event),
+                None => events.push(event),
+            },
+        }
+    }
+
+    let mut blocked_names: Vec<_> = blocked.iter().collect();
+    blocked_names.sort();
+    for (name, reason) in blocked_names {
+        console::warn(&format!(
+            "The footnote `{}` in {} contains {} and is rendered at the bottom of the page \
+             instead of as a sidenote",
+            name,
+            context.current_page_path.unwrap_or("unknown"),
+            reason,
+        ));
+    }
+
+    // step 2: render the sidenotes in place of the references, and the others at the bottom
+    let mut sidenotes =
+        Sidenotes { context, definitions, blocked, numbers: HashMap::new(), bottom: Vec::new() };
+    *old_events = sidenotes.convert(events, false)?;
+    let bottom = sidenotes.bottom_footnotes()?;
+    old_events.extend(bottom);
+
+    Ok(())
+}
+
+/// Whether there is anything else than closing tags after the event at `idx`
+fn has_content_after(events: &[Event], idx: usize) -> bool {
+    events.iter().skip(idx + 1).any(|e| !matches!(e, Event::End(_)))
+}
+
+/// Finds where the summary should end according to `auto_summary` for content without a
+/// `<!-- more -->` marker and inserts the same `CONTINUE_READING` marker there.
+/// Returns whether a marker was inserted, which is not the case if the content is shorter than
+/// the summary would be.
+fn insert_auto_summary_cutoff(events: &mut Vec<Event>, auto_summary: AutoSummary) -> bool {
+    let (by_words, mut remaining_words) = match auto_summary {
+        AutoSummary::None | AutoSummary::Words(0) => return false,
+        AutoSummary::Paragraph => (false, 0),
+        AutoSummary::Words(n) => (true, n),
+    };
+    let mut in_footnote = false;
+    // Text can be split in several events in the middle of a word so we need to keep track of it
+    let mut in_word = false;
+
+    for i in 0..events.len() {
+        match &events[i] {
+            Event::Start(Tag::FootnoteDefinition(_)) => in_footnote = true,
+            Event::End(TagEnd::FootnoteDefinition) => in_footnote = false,
+            _ if in_footnote => (),
+            Event::End(TagEnd::Paragraph) if !by_words => {
+                if !has_content_after(events, i) {
+                    return false;
+                }
+                events.insert(i + 1, Event::Html(CONTINUE_READING.into()));
+                return true;
+            }
+            Event::Text(text) if by_words => {
+                let mut cutoff = None;
+                for (idx, c) in text.char_indices() {
+                    if c.is_whitespace() {
+                        if in_word && remaining_words == 0 {
+                            cutoff = Some(idx);
+                            break;
+                        }
+                        in_word = false;
+                    } else if !in_word {
+                        if remaining_words == 0 {
+                            cutoff = Some(idx);
+                            break;
+                        }
+                        in_word = true;
+                        remaining_words -= 1;
                     }
                 }
-                has_written_backrefs = true;
-                if f == Event::End(TagEnd::FootnoteDefinition) {
-                    end.push_str("</li>\n");
-                } else {
-                    end.push_str("</p>\n");
+
+                if let Some(idx) = cutoff {
+                    let (before, after) = text.split_at(idx);
+                    if after.trim().is_empty() && !has_content_after(events, i) {
+                        return false;
+                    }
+                    let (before, after) = (before.trim_end().to_owned(), after.to_owned());
+                    events[i] = Event::Text(before.into());
+                    events.insert(i + 1, Event::Html(CONTINUE_READING.into()));
+                    events.insert(i + 2, Event::Text(after.into()));
+                    return true;
                 }
-                Event::Html(end.into())
             }
-            Event::End(TagEnd::FootnoteDefinition) => Event::Html("</li>\n".into()),
-            Event::FootnoteReference(_) => unreachable!("converted to HTML earlier"),
-            f => f,
-        })
-    });
+            Event::Code(code) if by_words => {
+                in_word = false;
+                remaining_words = remaining_words.saturating_sub(code.split_whitespace().count());
+                if remaining_words == 0 {
+                    if !has_content_after(events, i) {
+                        return false;
+                    }
+                    events.insert(i + 1, Event::Html(CONTINUE_READING.into()));
+                    return true;
+                }
+            }
+            // Inline formatting can happen in the middle of a word
+            Event::Start(Tag::Emphasis | Tag::Strong | Tag::Strikethrough)
+            | Event::End(TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough) => (),
+            // The previous text ended exactly on the last word of the summary
+            _ if by_words && remaining_words == 0 => {
+                if !has_content_after(events, i - 1) {
+                    return false;
+                }
+                events.insert(i, Event::Html(CONTINUE_READING.into()));
+                return true;
+            }
+            _ => in_word = false,
+        }
+    }
 
-    old_events.extend(footnotes);
-    old_events.push(Event::Html("</ol>\n</section>\n".into()));
+    false
+}
+
+/// The options of the markdown parser for the site
+pub(crate) fn parser_options(config: &Config) -> Options {
+    let mut opts = Options::empty();
+    opts.insert(Options::ENABLE_TABLES);
+    opts.insert(Options::ENABLE_FOOTNOTES);
+    opts.insert(Options::ENABLE_STRIKETHROUGH);
+    opts.insert(Options::ENABLE_TASKLISTS);
+    opts.insert(Options::ENABLE_HEADING_ATTRIBUTES);
+
+    if config.markdown.smart_punctuation {
+        opts.insert(Options::ENABLE_SMART_PUNCTUATION);
+    }
+    if config.markdown.definition_list {
+        opts.insert(Options::ENABLE_DEFINITION_LIST);
+    }
+    if config.markdown.github_alerts {
+        opts.insert(Options::ENABLE_GFM);
+    }
+    if config.markdown.wiki_links {
+        opts.insert(Options::ENABLE_WIKILINKS);
+    }
+    opts
 }
 
 pub fn markdown_to_html(
@@ -416,6 +833,10 @@ pub fn markdown_to_html(
     let mut error = None;
 
     let mut code_block: Option<CodeBlock> = None;
+    let mut code_block_count = 0;
+    // The callouts of the code block that just ended, and the list annotating them if any
+    let mut code_block_callouts = None;
+    let mut callout_annotations: Option<CalloutAnnotations> = None;
     // Indicates whether we're in the middle of parsing a text node which will be placed in an HTML
     // attribute, and which hence has to be escaped using escape_html rather than push_html's
     // default HTML body escaping for text nodes.
@@ -426,26 +847,46 @@ pub fn markdown_to_html(
     let mut external_links = Vec::new();
 
     let mut stop_next_end_p = false;
+    let mut empty_link_text = None;
+    // The anchors of the links to a heading of the current page without text, in order
+    let mut empty_link_anchors = vec![];
+
+    let glossary = context.glossary.filter(|g| !g.is_empty());
+    // Terms are only linked on their first use, and never on the page they link to
+    let mut used_glossary_terms: HashSet<usize> = HashSet::new();
+    // Glossary terms are not looked for in headings, links and images
+    let mut glossary_skip_depth = 0;
+    if let (Some(glossary), Some(path)) = (glossary, context.current_page_path) {
+        let own_link = format!("@/{}", path);
+        for (i, term) in glossary.terms.iter().enumerate() {
+            if term.link.as_deref() == Some(own_link.as_str()) {
+                used_glossary_terms.insert(i);
+            }
+        }
+    }
 
-    let lazy_async_image = context.config.markdown.lazy_async_image;
+    let attributes_enabled = context.config.markdown.attributes;
+    // Where the images and links being parsed start in `events`, and whether they are images
+    let mut inline_starts = vec![];
+    // The image or link that ended right before the current event, as its attributes follow it
+    let mut inline_ended = None;
+    let mut paragraph_start = 0;
+    let mut table_start = None;
 
-    let mut opts = Options::empty();
+    let lazy_async_image = context.config.markdown.lazy_async_image;
+    let inline_code_enabled = context.config.markdown.highlight_inline_code;
+    let inline_code_prefix =
+        context.config.markdown.inline_code_prefix.as_deref().filter(|_| inline_code_enabled);
+    // Inline code is not highlighted in headings as their text is used for their ids
+    let mut in_heading = false;
+
+    let sidenotes_enabled = context.config.markdown.sidenotes;
+    // The footnote definitions we are in, and the footnotes that cannot be sidenotes
+    let mut footnote_stack: Vec<String> = vec![];
+    let mut sidenote_blockers: HashMap<String, &'static str> = HashMap::new();
+
+    let opts = parser_options(context.config);
     let mut has_summary = false;
-    opts.insert(Options::ENABLE_TABLES);
-    opts.insert(Options::ENABLE_FOOTNOTES);
-    opts.insert(Options::ENABLE_STRIKETHROUGH);
-    opts.insert(Options::ENABLE_TASKLISTS);
-    opts.insert(Options::ENABLE_HEADING_ATTRIBUTES);
-
-    if context.config.markdown.smart_punctuation {
-        opts.insert(Options::ENABLE_SMART_PUNCTUATION);
-    }
-    if context.config.markdown.definition_list {
-        opts.insert(Options::ENABLE_DEFINITION_LIST);
-    }
-    if context.config.markdown.github_alerts {
-        opts.insert(Options::ENABLE_GFM);
-    }
 
     // we reverse their order so we can pop them easily in order
     let mut html_shortcodes: Vec<_> = html_shortcodes.into_iter().rev().collect();
@@ -514,6 +955,89 @@ pub fn markdown_to_html(
 
         let mut accumulated_block = String::new();
         for (event, mut range) in Parser::new_ext(content, opts).into_offset_iter() {
+            let previous_inline = inline_ended.take();
+            if sidenotes_enabled {
+                match event {
+                    Event::Start(Tag::FootnoteDefinition(ref name)) => {
+                        footnote_stack.push(name.to_string())
+                    }
+                    Event::End(TagEnd::FootnoteDefinition) => {
+                        footnote_stack.pop();
+                    }
+                    _ => {
+                        if let (Some(name), Some(blocker)) =
+                            (footnote_stack.last(), sidenote_blocker(&event))
+                        {
+                            sidenote_blockers.entry(name.clone()).or_insert(blocker);
+                        }
+                    }
+                }
+            }
+            match event {
+                Event::Start(Tag::Heading { .. }) => {
+                    glossary_skip_depth += 1;
+                    in_heading = true;
+                }
+                Event::Start(Tag::Link { .. }) => {
+                    glossary_skip_depth += 1;
+                    inline_starts.push((events.len(), false));
+                }
+                Event::Start(Tag::Image { .. }) => {
+                    glossary_skip_depth += 1;
+                    inline_starts.push((events.len(), true));
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    glossary_skip_depth -= 1;
+                    in_heading = false;
+                }
+                Event::End(TagEnd::Link | TagEnd::Image) => {
+                    glossary_skip_depth -= 1;
+                    inline_ended = inline_starts.pop();
+                }
+                Event::Start(Tag::Table(_)) => table_start = Some(events.len()),
+                _ => (),
+            }
+
+            let previous_callouts = code_block_callouts.take();
+            if let Some(ref mut annotations) = callout_annotations {
+                let (callout_id, annotation_id) =
+                    callout_ids(annotations.block, annotations.number);
+                match event {
+                    Event::Start(Tag::List(_)) => annotations.depth += 1,
+                    Event::End(TagEnd::List(_)) if annotations.depth > 0 => annotations.depth -= 1,
+                    Event::End(TagEnd::List(_)) => callout_annotations = None,
+                    Event::Start(Tag::Item) if annotations.depth == 0 => {
+                        events.push(Event::Html(format!("<li id=\"{}\">", annotation_id).into()));
+                        continue;
+                    }
+                    Event::End(TagEnd::Item) if annotations.depth == 0 => {
+                        let backref = if annotations.callouts.contains(&annotations.number) {
+                            format!(
+                                r##" <a class="code-callout-backref" href="#{}">↩</a>"##,
+                                callout_id
+                            )
+                        } else {
+                            String::new()
+                        };
+                        events.push(Event::Html(format!("{}</li>\n", backref).into()));
+                        annotations.number += 1;
+                        continue;
+                    }
+                    _ => (),
+                }
+            } else if let (Some((block, callouts)), Event::Start(Tag::List(Some(first)))) =
+                (previous_callouts, &event)
+            {
+                let start =
+                    if *first == 1 { String::new() } else { format!(" start=\"{}\"", first) };
+                events.push(Event::Html(
+                    format!("<ol class=\"code-annotations\"{}>\n", start).into(),
+                ));
+                callout_annotations =
+                    Some(CalloutAnnotations { block, callouts, number: *first as usize, depth: 0 });
+                continue;
+            }
+
             match event {
                 Event::Text(text) => {
                     if let Some(ref mut _code_block) = code_block {
@@ -542,6 +1066,72 @@ pub fn markdown_to_html(
                             accumulated_block += &text;
                         }
                     } else {
+                        // `{...}` attributes right after an image or a link
+                        let text = match previous_inline.filter(|_| attributes_enabled) {
+                            Some((start, is_image)) => match parse_attributes(&text) {
+                                Some((attributes, len)) => {
+                                    if is_image {
+                                        let html =
+                                            render_with_attributes(&events[start..], &attributes);
+                                        events.truncate(start);
+                                        events.push(Event::Html(html.into()));
+                                    } else {
+                                        let html = render_with_attributes(
+                                            &events[start..=start],
+                                            &attributes,
+                                        );
+                                        events[start] = Event::Html(html.into());
+                                        *events.last_mut().unwrap() = Event::Html("</a>".into());
+                                    }
+                                    if len == text.len() {
+                                        continue;
+                                    }
+                                    text[len..].to_string().into()
+                                }
+                                None => text,
+                            },
+                            None => text,
+                        };
+                        // `code`{:lang} is highlighted in that language
+                        let lang_hint = match events.last() {
+                            Some(Event::Code(code)) if inline_code_enabled => {
+                                INLINE_CODE_LANG_RE.captures(&text).map(|caps| {
+                                    let html = if in_heading {
+                                        None
+                                    } else {
+                                        Some(highlight_inline_code(
+                                            code,
+                                            &caps[1],
+                                            context.config,
+                                            path,
+                                        ))
+                                    };
+                                    (caps[0].len(), html)
+                                })
+                            }
+                            _ => None,
+                        };
+                        let text = match lang_hint {
+                            Some((len, html)) => {
+                                match html {
+                                    Some(Ok(html)) => {
+                                        *events.last_mut().unwrap() = Event::Html(html.into())
+                                    }
+                                    Some(Err(e)) => {
+                                        error = Some(e);
+                                        break;
+                                    }
+                                    None => (),
+                                }
+                                range.start += len;
+                                if len == text.len() {
+                                    continue;
+                                }
+                                text[len..].to_string().into()
+                            }
+                            None => text,
+                        };
+
                         let text = if context.config.markdown.render_emoji {
                             EMOJI_REPLACER.replace_all(&text).to_string().into()
                         } else {
@@ -553,6 +1143,23 @@ pub fn markdown_to_html(
                                 let mut buffer = "".to_string();
                                 escape_html(&mut buffer, text.as_ref()).unwrap();
                                 events.push(Event::Html(buffer.into()));
+                            } else if let Some(glossary) =
+                                glossary.filter(|_| glossary_skip_depth == 0)
+                            {
+                                match insert_glossary_terms(
+                                    text,
+                                    glossary,
+                                    context,
+                                    &mut used_glossary_terms,
+                                    &mut internal_links,
+                                    &mut external_links,
+                                ) {
+                                    Ok(glossary_events) => events.extend(glossary_events),
+                                    Err(e) => {
+                                        error = Some(e);
+                                        break;
+                                    }
+                                }
                             } else {
                                 events.push(Event::Text(text));
                             }
@@ -562,19 +1169,53 @@ pub fn markdown_to_html(
                         render_shortcodes!(true, text, range);
                     }
                 }
+                Event::Code(code) if inline_code_prefix.is_some() => {
+                    let prefixed = code
+                        .strip_prefix(inline_code_prefix.unwrap())
+                        .and_then(|rest| rest.split_once(' '))
+                        .filter(|(lang, _)| {
+                            !lang.is_empty()
+                                && lang.chars().all(|c| c.is_alphanumeric() || "_+#.-".contains(c))
+                        });
+                    match prefixed {
+                        Some((_, code)) if in_heading => {
+                            events.push(Event::Code(code.to_string().into()))
+                        }
+                        Some((lang, code)) => {
+                            match highlight_inline_code(code, lang, context.config, path) {
+                                Ok(html) => events.push(Event::Html(html.into())),
+                                Err(e) => {
+                                    error = Some(e);
+                                    break;
+                                }
+                            }
+                        }
+                        None => events.push(Event::Code(code)),
+                    }
+                }
                 Event::Start(Tag::CodeBlock(ref kind)) => {
-                    let fence = match kind {
-                        cmark::CodeBlockKind::Fenced(fence_info) => FenceSettings::new(fence_info),
-                        _ => FenceSettings::new(""),
-                    };
-                    let (block, begin) = match CodeBlock::new(fence, context.config, path) {
-                        Ok(cb) => cb,
-                        Err(e) => {
-                            error = Some(e);
-                            break;
+                    let (fence_info, fence_attributes) = match kind {
+                        cmark::CodeBlockKind::Fenced(fence_info) if attributes_enabled => {
+                            split_fence_attributes(fence_info)
                         }
+                        cmark::CodeBlockKind::Fenced(fence_info) => (fence_info.as_ref(), None),
+                        _ => ("", None),
                     };
+                    let fence = FenceSettings::new(fence_info);
+                    code_block_count += 1;
+                    let (block, begin) =
+                        match CodeBlock::new(fence, context.config, path, code_block_count) {
+                            Ok(cb) => cb,
+                            Err(e) => {
+                                error = Some(e);
+                                break;
+                            }
+                        };
                     code_block = Some(block);
+                    let begin = match fence_attributes {
+                        Some(attributes) => attributes.apply_to_tag(&begin),
+                        None => begin,
+                    };
                     events.push(Event::Html(begin.into()));
                 }
                 Event::End(TagEnd::CodeBlock { .. }) => {
@@ -582,6 +1223,10 @@ pub fn markdown_to_html(
                         let html = code_block.highlight(&accumulated_block);
                         events.push(Event::Html(html.into()));
                         accumulated_block.clear();
+                        if !code_block.callouts().is_empty() {
+                            code_block_callouts =
+                                Some((code_block_count, code_block.callouts().to_vec()));
+                        }
                     }
 
                     // reset highlight and close the code block
@@ -632,6 +1277,7 @@ pub fn markdown_to_html(
                     }));
                 }
                 Event::Start(Tag::Link { link_type, dest_url, title, id }) => {
+                    let internal_links_len = internal_links.len();
                     let fixed_link = match fix_link(
                         link_type,
                         &dest_url,
@@ -647,6 +1293,21 @@ pub fn markdown_to_html(
                         }
                     };
 
+                    // Internal links without text get the title of what they point to, the
+                    // headings of other pages only being known once they are rendered
+                    empty_link_text = match internal_links.get(internal_links_len) {
+                        Some((md_path, Some(anchor)))
+                            if context.current_page_path == Some(md_path.as_str()) =>
+                        {
+                            Some(EmptyLinkText::Heading(anchor.clone()))
+                        }
+                        Some((md_path, _)) => Some(match context.titles.get(md_path) {
+                            Some(title) => EmptyLinkText::Title(title.clone()),
+                            None => EmptyLinkText::Url(fixed_link.clone()),
+                        }),
+                        None => None,
+                    };
+
                     events.push(
                         if is_external_link(&dest_url)
                             && context.config.markdown.has_external_link_tweaks()
@@ -672,6 +1333,26 @@ pub fn markdown_to_html(
                         },
                     )
                 }
+                Event::End(TagEnd::Link) => {
+                    if let Some(text) = empty_link_text.take() {
+                        if matches!(events.last(), Some(Event::Start(Tag::Link { .. }))) {
+                            match text {
+                                EmptyLinkText::Title(title) => {
+                                    events.push(Event::Text(title.into()))
+                                }
+                                EmptyLinkText::Heading(anchor) => {
+                                    events.push(Event::Html(EMPTY_LINK_TEXT.into()));
+                                    empty_link_anchors.push(anchor);
+                                }
+                                EmptyLinkText::Url(url) => {
+                                    warn_unfilled_link(&url, context);
+                                    events.push(Event::Text(url.into()));
+                                }
+                            }
+                        }
+                    }
+                    events.push(event);
+                }
                 Event::Start(Tag::Paragraph) => {
                     // We have to compare the start and the trimmed length because the content
                     // will sometimes contain '\n' at the end which we want to avoid.
@@ -688,9 +1369,43 @@ pub fn markdown_to_html(
                         }
                     }
 
+                    paragraph_start = events.len();
                     events.push(event);
                 }
                 Event::End(TagEnd::Paragraph) => {
+                    let attributes = match events.last() {
+                        Some(Event::Text(text)) if attributes_enabled && !stop_next_end_p => {
+                            parse_only_attributes(text)
+                        }
+                        _ => None,
+                    };
+                    if let Some(attributes) = attributes {
+                        let len = events.len();
+                        let after_table = paragraph_start > 0
+                            && matches!(events[paragraph_start - 1], Event::End(TagEnd::Table));
+                        if len == paragraph_start + 2 && after_table {
+                            // A paragraph of only attributes right after a table applies to it
+                            let start = table_start.take().unwrap();
+                            events.truncate(paragraph_start);
+                            let html = render_with_attributes(&events[start..], &attributes);
+                            events.truncate(start);
+                            events.push(Event::Html(html.into()));
+                            continue;
+                        } else if len > paragraph_start + 2
+                            && matches!(events[len - 2], Event::SoftBreak)
+                        {
+                            // On the last line of a paragraph they apply to the paragraph
+                            events.truncate(len - 2);
+                            let html = render_with_attributes(
+                                &events[paragraph_start..=paragraph_start],
+                                &attributes,
+                            );
+                            events[paragraph_start] = Event::Html(html.into());
+                            events.push(Event::Html("</p>\n".into()));
+                            continue;
+                        }
+                    }
+
                     events.push(if stop_next_end_p {
                         stop_next_end_p = false;
                         Event::Html("".into())
@@ -719,6 +1434,10 @@ pub fn markdown_to_html(
             _ => true,
         });
 
+        if let Some(typography) = Typography::new(context.config, context.lang) {
+            typography.apply(&mut events);
+        }
+
         let heading_refs = get_heading_refs(&events);
 
         let mut anchors_to_insert = vec![];
@@ -790,14 +1509,36 @@ pub fn markdown_to_html(
             headings.push(h);
         }
 
+        if !empty_link_anchors.is_empty() {
+            let mut anchors = empty_link_anchors.into_iter();
+            for event in events.iter_mut() {
+                if !matches!(event, Event::Html(CowStr::Borrowed(EMPTY_LINK_TEXT))) {
+                    continue;
+                }
+                if let Some(anchor) = anchors.next() {
+                    let title = headings.iter().find(|h| h.id == anchor).map(|h| h.title.clone());
+                    let text = title.unwrap_or_else(|| {
+                        let url = format!("#{}", anchor);
+                        warn_unfilled_link(&url, context);
+                        url
+                    });
+                    *event = Event::Text(text.into());
+                }
+            }
+        }
+
         if context.insert_anchor != InsertAnchor::None {
             insert_many(&mut events, anchors_to_insert);
         }
 
-        if context.config.markdown.bottom_footnotes {
+        if context.config.markdown.bottom_footnotes && !context.config.markdown.sidenotes {
             convert_footnotes_to_github_style(&mut events);
         }
 
+        if !has_summary {
+            has_summary = insert_auto_summary_cutoff(&mut events, context.auto_summary);
+        }
+
         let continue_reading = events
             .iter()
             .position(|e| matches!(e, Event::Html(CowStr::Borrowed(CONTINUE_READING))))
@@ -853,6 +1594,11 @@ pub fn markdown_to_html(
             summary = Some(summary_html);
         }
 
+        // Done after the summary, which keeps the footnotes where they are defined
+        if context.config.markdown.sidenotes {
+            convert_footnotes_to_sidenotes(&mut events, context, &sidenote_blockers)?;
+        }
+
         // emit everything after summary
         cmark::html::push_html(&mut html, events.into_iter());
     }
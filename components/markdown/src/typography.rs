@@ -0,0 +1,189 @@
+//! Language-aware typography: quotes and French spacing when `smart_punctuation` is enabled,
+//! and soft hyphens for the languages in `hyphenate`.
+use config::{Config, Markdown};
+use libs::hypher::{self, Lang};
+use libs::once_cell::sync::Lazy;
+use libs::pulldown_cmark::{Event, Tag, TagEnd};
+use libs::regex::Regex;
+
+const NBSP: char = '\u{a0}';
+const NARROW_NBSP: char = '\u{202f}';
+const SOFT_HYPHEN: &str = "\u{ad}";
+
+static WORD_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\p{Alphabetic}+").unwrap());
+/// Spaces that French typography wants non-breaking, before `:;?!»` and after `«`
+static FRENCH_SPACE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(«)[ \u{a0}]*|[ \u{a0}]*(»)| +([:;?!])").unwrap());
+
+/// The primary language subtag, `fr` for `fr-CA`
+fn primary_language(lang: &str) -> &str {
+    lang.split(['-', '_']).next().unwrap_or(lang)
+}
+
+/// The opening and closing double quotes, then single quotes, of a language.
+/// Languages not listed use the English ones.
+fn quotes_for_lang(lang: &str) -> [char; 4] {
+    match primary_language(lang) {
+        "de" | "cs" | "sk" => ['„', '“', '‚', '‘'],
+        "fr" | "es" | "it" | "pt" => ['«', '»', '“', '”'],
+        "ru" | "uk" => ['«', '»', '„', '“'],
+        "pl" => ['„', '”', '«', '»'],
+        "ja" => ['「', '」', '『', '』'],
+        _ => ['“', '”', '‘', '’'],
+    }
+}
+
+pub struct Typography {
+    /// The quotes replacing the English ones of smart punctuation
+    quotes: Option<[char; 4]>,
+    french_spacing: bool,
+    hyphenation: Option<Lang>,
+}
+
+impl Typography {
+    /// Returns `None` if there is nothing to change in that language
+    pub fn new(config: &Config, lang: &str) -> Option<Typography> {
+        let smart_punctuation = config.markdown.smart_punctuation;
+        let quotes = Some(quotes_for_lang(lang))
+            .filter(|q| smart_punctuation && q != &quotes_for_lang("en"));
+        let french_spacing = smart_punctuation && primary_language(lang) == "fr";
+        let hyphenation = if config.markdown.hyphenate.iter().any(|l| l == lang) {
+            Markdown::hyphenation_lang(lang)
+        } else {
+            None
+        };
+
+        if quotes.is_none() && !french_spacing && hyphenation.is_none() {
+            return None;
+        }
+        Some(Typography { quotes, french_spacing, hyphenation })
+    }
+
+    /// Swaps the quotes of smart punctuation for the ones of the language. A `’` only closes a
+    /// single quote if one is open and it is not followed by a letter, otherwise it is an apostrophe.
+    fn localize_quotes(&self, text: &str, quotes: [char; 4], open_singles: &mut usize) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '“' => out.push(quotes[0]),
+                '”' => out.push(quotes[1]),
+                '‘' => {
+                    *open_singles += 1;
+                    out.push(quotes[2]);
+                }
+                '’' if *open_singles > 0 && !chars.peek().is_some_and(|c| c.is_alphanumeric()) => {
+                    *open_singles -= 1;
+                    out.push(quotes[3]);
+                }
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    fn apply_to_text(&self, text: &str, open_singles: &mut usize, in_heading: bool) -> String {
+        let mut text = match self.quotes {
+            Some(quotes) => self.localize_quotes(text, quotes, open_singles),
+            None => text.to_string(),
+        };
+        if self.french_spacing {
+            text = FRENCH_SPACE_RE
+                .replace_all(&text, |caps: &libs::regex::Captures| {
+                    if caps.get(1).is_some() {
+                        format!("«{}", NBSP)
+                    } else if caps.get(2).is_some() {
+                        format!("{}»", NBSP)
+                    } else if &caps[3] == ":" {
+                        format!("{}:", NBSP)
+                    } else {
+                        format!("{}{}", NARROW_NBSP, &caps[3])
+                    }
+                })
+                .into_owned();
+        }
+        // Headings are left alone as soft hyphens would end up in their ids
+        match self.hyphenation {
+            Some(lang) if !in_heading => WORD_RE
+                .replace_all(&text, |caps: &libs::regex::Captures| {
+                    hypher::hyphenate(&caps[0], lang).join(SOFT_HYPHEN)
+                })
+                .into_owned(),
+            _ => text,
+        }
+    }
+
+    /// Applies the typography of the language to the text events
+    pub fn apply(&self, events: &mut [Event]) {
+        let mut open_singles = 0;
+        let mut in_heading = false;
+        for event in events.iter_mut() {
+            match event {
+                Event::Text(text) => {
+                    *text = self.apply_to_text(text, &mut open_singles, in_heading).into();
+                }
+                Event::Start(Tag::Heading { .. }) => in_heading = true,
+                Event::End(TagEnd::Heading(_)) => {
+                    in_heading = false;
+                    open_singles = 0;
+                }
+                Event::End(TagEnd::Paragraph | TagEnd::Item | TagEnd::TableCell) => {
+                    open_singles = 0;
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(smart_punctuation: bool, hyphenate: &[&str]) -> Config {
+        let mut config = Config::default_for_test();
+        config.markdown.smart_punctuation = smart_punctuation;
+        config.markdown.hyphenate = hyphenate.iter().map(|l| l.to_string()).collect();
+        config
+    }
+
+    fn apply(config: &Config, lang: &str, text: &str) -> String {
+        let typography = Typography::new(config, lang).unwrap();
+        let mut events = vec![Event::Text(text.into())];
+        typography.apply(&mut events);
+        match &events[0] {
+            Event::Text(t) => t.to_string(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn does_nothing_in_english_by_default() {
+        assert!(Typography::new(&config(true, &[]), "en").is_none());
+        assert!(Typography::new(&config(false, &[]), "fr").is_none());
+    }
+
+    #[test]
+    fn can_localize_quotes() {
+        let config = config(true, &[]);
+        assert_eq!(apply(&config, "de", "“Er sagt ‘Hallo’”, don’t"), "„Er sagt ‚Hallo‘“, don’t");
+        assert_eq!(apply(&config, "ja", "“こんにちは”"), "「こんにちは」");
+        assert_eq!(apply(&config, "de-CH", "‘l’eau’"), "‚l’eau‘");
+    }
+
+    #[test]
+    fn can_add_french_spaces() {
+        let config = config(true, &[]);
+        assert_eq!(
+            apply(&config, "fr", "Il dit “bonjour” : vraiment ? Oui ! à 10:30; « déjà »"),
+            "Il dit «\u{a0}bonjour\u{a0}»\u{a0}: vraiment\u{202f}? Oui\u{202f}! à 10:30; «\u{a0}déjà\u{a0}»"
+        );
+    }
+
+    #[test]
+    fn can_hyphenate_configured_languages() {
+        let config = config(false, &["en"]);
+        assert_eq!(apply(&config, "en", "extensive"), "ex\u{ad}ten\u{ad}sive");
+        assert!(Typography::new(&config, "fr").is_none());
+    }
+}
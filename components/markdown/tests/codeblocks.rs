@@ -390,3 +390,143 @@ bar
     );
     insta::assert_snapshot!(body);
 }
+
+#[test]
+fn can_mark_diff_lines() {
+    let body = render_codeblock(
+        r#"
+```rust,diff,hl_lines=3
+ fn main() {
+-    println!("Hello");
++    println!("Hello, world!");
+ }
+```
+    "#,
+        HighlightMode::Classed,
+    );
+    insta::assert_snapshot!(body);
+}
+
+#[test]
+fn can_highlight_diff_language() {
+    let body = render_codeblock(
+        r#"
+```diff
+-old
++new
+```
+    "#,
+        HighlightMode::Classed,
+    );
+    assert!(!body.contains("data-diff"));
+    assert!(body.contains(r#"data-lang="diff""#));
+}
+
+#[test]
+fn can_link_code_callouts_to_annotations() {
+    let mut config = Config::default_for_test();
+    config.markdown.highlight_code = true;
+    config.markdown.highlight_theme = "css".to_owned();
+    config.markdown.code_callouts = Some(r"// \((\d+)\)".to_owned());
+    config.markdown.init_code_callouts().unwrap();
+
+    let markdown = r#"
+```rust
+fn main() { // (1)
+    println!("Hello"); // (2)
+}
+```
+
+1. The entry point
+2. Prints to the terminal:
+   - with a newline
+3. Not a callout
+
+```rust
+let x = 1; // (1)
+```
+
+Not annotated.
+"#;
+    let body = common::render_with_config(markdown, config).unwrap().body;
+    insta::assert_snapshot!(body);
+}
+
+#[test]
+fn can_highlight_ansi_terminal_output() {
+    let markdown = "```terminal\n\u{1b}[1;32mok\u{1b}[0m done\n```\n";
+    let inlined = render_codeblock(markdown, HighlightMode::Inlined);
+    assert!(inlined.contains(r#"<span style="color:#0dbc79;font-weight:bold;">ok</span> done"#));
+    assert!(inlined.starts_with(r#"<pre data-lang="terminal" style="background-color:"#));
+
+    let classed = render_codeblock(markdown, HighlightMode::Classed);
+    assert!(classed.contains(r#"<span class="z-ansi-green z-ansi-bold">ok</span> done"#));
+    assert!(classed.contains(r#"class="language-terminal z-code""#));
+
+    let plain = render_codeblock(markdown, HighlightMode::None);
+    assert!(plain.contains("<code class=\"language-terminal\" data-lang=\"terminal\">ok done\n"));
+}
+
+#[test]
+fn can_highlight_inline_code() {
+    let markdown = r#"
+# The `main`{:rust} function
+
+Call `let x = 5;`{:rust}, `#!py print("hi")` or `{:rust}` but not `plain`.
+"#;
+    let mut outputs = vec![];
+    for theme in ["base16-ocean-dark", "css"] {
+        let mut config = Config::default_for_test();
+        config.markdown.highlight_code = true;
+        config.markdown.highlight_theme = theme.to_owned();
+        config.markdown.highlight_inline_code = true;
+        config.markdown.inline_code_prefix = Some("#!".to_owned());
+        outputs.push(common::render_with_config(markdown, config).unwrap().body);
+    }
+    insta::assert_snapshot!(outputs.join("\n"));
+}
+
+#[cfg(feature = "tree-sitter")]
+#[test]
+fn can_highlight_with_tree_sitter() {
+    let markdown = r#"
+```rust,linenos,hl_lines=2,hide_lines=4
+fn main() {
+    let s = "multi
+line";
+    hidden();
+}
+```
+
+```zig
+const x = 1;
+```
+
+```nix
+{ x = "a"; }
+```
+
+```lua
+local x = 1
+```
+"#;
+    let mut outputs = vec![];
+    for theme in ["base16-ocean-dark", "css"] {
+        let mut config = Config::default_for_test();
+        config.markdown.highlight_code = true;
+        config.markdown.highlight_theme = theme.to_owned();
+        config.markdown.highlighter = config::Highlighter::TreeSitter;
+        outputs.push(common::render_with_config(markdown, config).unwrap().body);
+    }
+    insta::assert_snapshot!(outputs.join("\n"));
+}
+
+#[cfg(not(feature = "tree-sitter"))]
+#[test]
+fn errors_on_tree_sitter_without_the_feature() {
+    let mut config = Config::default_for_test();
+    config.markdown.highlight_code = true;
+    config.markdown.highlighter = config::Highlighter::TreeSitter;
+    let res = common::render_with_config("```rust\nfn main() {}\n```\n", config);
+    assert!(res.is_err());
+}
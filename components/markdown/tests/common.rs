@@ -8,6 +8,7 @@ use config::Config;
 use errors::Result;
 use markdown::{render_content, RenderContext, Rendered};
 use templates::ZOLA_TERA;
+use utils::site::WikiLinkTargets;
 use utils::types::InsertAnchor;
 
 fn configurable_render(
@@ -57,6 +58,14 @@ fn configurable_render(
 
     let mut permalinks = HashMap::new();
     permalinks.insert("pages/about.md".to_owned(), "https://getzola.org/about/".to_owned());
+    permalinks.insert("pages/contact.md".to_owned(), "https://getzola.org/contact/".to_owned());
+    let mut wiki_link_targets = WikiLinkTargets::default();
+    wiki_link_targets.insert("en", &["About us", "about"], "pages/about.md");
+    wiki_link_targets.insert("en", &["Contact", "about-contact"], "pages/contact.md");
+    wiki_link_targets.insert("en", &["Team"], "pages/about.md");
+    wiki_link_targets.insert("en", &["Team"], "pages/contact.md");
+    let mut titles = HashMap::new();
+    titles.insert("pages/about.md".to_owned(), "About us".to_owned());
 
     tera.register_filter(
         "markdown",
@@ -73,6 +82,11 @@ fn configurable_render(
     let shortcode_def = utils::templates::get_shortcodes(&tera);
     context.set_shortcode_definitions(&shortcode_def);
     context.set_current_page_path("my_page.md");
+    context.set_wiki_link_targets(&wiki_link_targets);
+    context.set_titles(&titles);
+    context.set_glossary(config.markdown.glossaries.get(&config.default_language));
+    // Pages get it from their section or the config
+    context.set_auto_summary(config.markdown.auto_summary);
 
     render_content(content, &context)
 }
@@ -53,3 +53,94 @@ Email: <foo@bar.baz>",
     assert!(res.is_err());
     assert_eq!(res.unwrap_err().to_string(), "There is a link that is missing a URL");
 }
+
+#[test]
+fn can_resolve_wiki_links() {
+    let mut config = config::Config::default_for_test();
+    config.markdown.wiki_links = true;
+
+    let rendered = common::render_with_config(
+        "[[about us]], [[Contact|reach out]], [[About us#Our history]] and [[#Intro text]]",
+        config.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        rendered.body,
+        "<p><a href=\"https://getzola.org/about/\">about us</a>, \
+         <a href=\"https://getzola.org/contact/\">reach out</a>, \
+         <a href=\"https://getzola.org/about/#our-history\">About us#Our history</a> and \
+         <a href=\"https://www.getzola.org/test/#intro-text\">#Intro text</a></p>\n"
+    );
+    assert_eq!(
+        rendered.internal_links,
+        vec![
+            ("pages/about.md".to_owned(), None),
+            ("pages/contact.md".to_owned(), None),
+            ("pages/about.md".to_owned(), Some("our-history".to_owned())),
+            ("my_page.md".to_owned(), Some("intro-text".to_owned())),
+        ]
+    );
+
+    let err = common::render_with_config("[[Team]]", config.clone()).unwrap_err();
+    assert!(format!("{:?}", err).contains("Wiki link `Team` is ambiguous"));
+    let err = common::render_with_config("[[Nothing]]", config).unwrap_err();
+    assert!(format!("{:?}", err).contains("Wiki link `Nothing` does not match any page"));
+
+    // Not enabled by default
+    let rendered = common::render("[[about us]]").unwrap();
+    assert_eq!(rendered.body, "<p>[[about us]]</p>\n");
+}
+
+#[test]
+fn can_fill_empty_internal_links() {
+    let rendered = common::render(
+        "[](@/pages/about.md), [](@/pages/about.md#our-history), [](#second-part), \
+         [](@/pages/contact.md), [](#missing) and [kept](@/pages/about.md)\n\n## Second `part`",
+    )
+    .unwrap();
+    assert_eq!(
+        rendered.body,
+        "<p><a href=\"https://getzola.org/about/\">About us</a>, \
+         <a href=\"https://getzola.org/about/#our-history\">About us</a>, \
+         <a href=\"https://www.getzola.org/test/#second-part\">Second part</a>, \
+         <a href=\"https://getzola.org/contact/\">https://getzola.org/contact/</a>, \
+         <a href=\"https://www.getzola.org/test/#missing\">#missing</a> and \
+         <a href=\"https://getzola.org/about/\">kept</a></p>\n\
+         <h2 id=\"second-part\">Second <code>part</code></h2>\n"
+    );
+}
+
+#[test]
+fn can_insert_glossary_terms() {
+    let mut config = config::Config::default_for_test();
+    let glossary = config::Glossary::new(vec![
+        config::GlossaryTerm {
+            term: "SSG".to_owned(),
+            title: Some("Static Site Generator".to_owned()),
+            link: None,
+        },
+        config::GlossaryTerm {
+            term: "Zola".to_owned(),
+            title: None,
+            link: Some("@/pages/about.md".to_owned()),
+        },
+    ])
+    .unwrap();
+    config.markdown.glossaries.insert("en".to_owned(), glossary);
+
+    let rendered = common::render_with_config(
+        "# Zola\n\nZola is an SSG, the SSG in `Zola`, not [Zola](https://example.com).\n\n\
+         Zola again, SSGs",
+        config,
+    )
+    .unwrap();
+    assert_eq!(
+        rendered.body,
+        "<h1 id=\"zola\">Zola</h1>\n\
+         <p><a class=\"glossary-term\" href=\"https://getzola.org/about/\">Zola</a> is an \
+         <abbr title=\"Static Site Generator\">SSG</abbr>, the SSG in <code>Zola</code>, \
+         not <a href=\"https://example.com\">Zola</a>.</p>\n\
+         <p>Zola again, SSGs</p>\n"
+    );
+    assert_eq!(rendered.internal_links, vec![("pages/about.md".to_owned(), None)]);
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use libs::tera::Tera;
 
-use config::Config;
+use config::{Config, LanguageOptions};
 use markdown::{render_content, RenderContext};
 use templates::ZOLA_TERA;
 use utils::slugs::SlugifyStrategy;
@@ -450,6 +450,114 @@ Footnotes can also be referenced with identifiers[^first].
     insta::assert_snapshot!(body);
 }
 
+#[test]
+fn sidenote_footnotes() {
+    let mut config = Config::default_for_test();
+    config.markdown.sidenotes = true;
+    // Sidenotes take precedence
+    config.markdown.bottom_footnotes = true;
+
+    let markdown = r#"This text has a footnote[^1] and another one[^long].
+
+[^1]: With *some* markup.
+
+[^long]: That has two paragraphs.
+
+    And a footnote[^nested] inside.
+
+[^nested]: At the bottom too.
+
+The first footnote is used again[^1].
+"#;
+
+    let body = common::render_with_config(markdown, config).unwrap().body;
+    insta::assert_snapshot!(body);
+}
+
+#[test]
+fn sidenote_footnotes_with_blocks_go_to_the_bottom() {
+    let mut config = Config::default_for_test();
+    config.markdown.sidenotes = true;
+
+    let markdown = r#"Inline[^inline], with a list[^list] and inline again[^inline].
+
+[^inline]: Just text.
+
+[^list]: Some steps:
+
+    - first
+    - second
+
+Code[^code] in a sidenote is fine.
+
+[^code]: With `code`.
+"#;
+
+    let body = common::render_with_config(markdown, config).unwrap().body;
+    insta::assert_snapshot!(body);
+}
+
+#[test]
+fn can_use_typography_of_page_language() {
+    let markdown = r#"# "L'été"
+
+Il a dit "c'est 'super' !" : vraiment ?
+
+`"code"` reste tel quel ; 10:30.
+"#;
+    let mut outputs = vec![];
+    for lang in ["fr", "de", "ja"] {
+        let mut config = Config::default_for_test();
+        config.markdown.smart_punctuation = true;
+        config.default_language = lang.to_string();
+        config.languages.insert(lang.to_string(), LanguageOptions::default());
+        config.markdown.hyphenate = vec!["fr".to_string()];
+        outputs.push(common::render_with_config(markdown, config).unwrap().body);
+    }
+    insta::assert_snapshot!(outputs.join("\n"));
+}
+
+#[test]
+fn can_set_attributes() {
+    let mut config = Config::default_for_test();
+    config.markdown.attributes = true;
+
+    let markdown = r#"![A cat](cat.png){.wide loading=eager} and [a link](https://example.com){#main title="The link"}, not {.this}
+
+A lead paragraph
+{.lead data-x='1 2'}
+
+| a | b |
+|---|:-:|
+| 1 | 2 |
+
+{.data-table}
+
+```rust,linenos {.wide data-file=main.rs}
+fn main() {}
+```
+
+Invalid {attributes} and [link](https://example.com){wide} are left as is
+{.not attributes}
+"#;
+
+    let body = common::render_with_config(markdown, config.clone()).unwrap().body;
+    insta::assert_snapshot!(body);
+
+    config.markdown.lazy_async_image = true;
+    let body = common::render_with_config("![A cat](cat.png){.wide loading=eager}", config.clone())
+        .unwrap()
+        .body;
+    assert_eq!(
+        body,
+        "<p><img src=\"https://www.getzola.org/test/cat.png\" alt=\"A cat\" loading=\"eager\" decoding=\"async\" class=\"wide\" /></p>\n"
+    );
+
+    // Not enabled by default
+    let body = common::render("[a link](https://example.com){#main}").unwrap().body;
+    assert_eq!(body, "<p><a href=\"https://example.com\">a link</a>{#main}</p>\n");
+}
+
 #[test]
 fn github_alerts() {
     let mut config = Config::default_for_test();
@@ -1,3 +1,6 @@
+use config::Config;
+use utils::types::AutoSummary;
+
 mod common;
 
 fn get_summary(content: &str) -> String {
@@ -8,6 +11,12 @@ fn get_rendered(content: &str) -> markdown::Rendered {
     common::render(content).expect("couldn't render")
 }
 
+fn get_auto_summary(content: &str, auto_summary: AutoSummary) -> Option<String> {
+    let mut config = Config::default_for_test();
+    config.markdown.auto_summary = auto_summary;
+    common::render_with_config(content, config).expect("couldn't render").summary
+}
+
 #[test]
 fn basic_summary() {
     let body = get_summary(
@@ -75,3 +84,52 @@ Good bye.
     );
     insta::assert_snapshot!(body);
 }
+
+#[test]
+fn auto_summary_first_paragraph() {
+    let summary = get_auto_summary(
+        r#"
+Hello **world**[^1].
+
+Good bye.
+
+[^1]: "World" is a placeholder.
+    "#,
+        AutoSummary::Paragraph,
+    );
+    insta::assert_snapshot!(summary.unwrap());
+}
+
+#[test]
+fn auto_summary_first_words_closes_tags() {
+    let summary = get_auto_summary(
+        r#"
+Things to do: **buy some
+milk**, eat and sleep.
+
+Good bye.
+    "#,
+        AutoSummary::Words(5),
+    );
+    insta::assert_snapshot!(summary.unwrap());
+}
+
+#[test]
+fn auto_summary_counts_words_split_in_several_events() {
+    let summary =
+        get_auto_summary("Hello *wor*ld, we're here. And some more", AutoSummary::Words(4));
+    insta::assert_snapshot!(summary.unwrap());
+}
+
+#[test]
+fn no_auto_summary_for_short_content() {
+    assert_eq!(get_auto_summary("Hello **world**", AutoSummary::Words(2)), None);
+    assert_eq!(get_auto_summary("Hello world", AutoSummary::Paragraph), None);
+}
+
+#[test]
+fn more_divider_has_priority_over_auto_summary() {
+    let summary =
+        get_auto_summary("Hello world.\n\nThis is <!-- more --> the end.", AutoSummary::Paragraph);
+    insta::assert_snapshot!(summary.unwrap());
+}
@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use config::{LintLevel, LintRule};
+use errors::{anyhow, Result};
+use markdown::lint_markdown;
+use utils::fs::read_file;
+
+use crate::Site;
+
+/// Something a content lint rule found in a page or section
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContentLintIssue {
+    pub level: LintLevel,
+    pub rule: LintRule,
+    /// The path of the file, relative to the site
+    pub path: String,
+    pub line: usize,
+    pub message: String,
+}
+
+/// The line of the file where the front matter sets `key`, 1 if it cannot be found
+fn front_matter_line(front_matter: &[&str], key: &str) -> usize {
+    front_matter
+        .iter()
+        .position(|line| {
+            line.trim_start()
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with(['=', ':']))
+        })
+        .map_or(1, |i| i + 1)
+}
+
+/// Runs the content lint rules on a page or section, skipping the ones it ignores
+fn lint_file(
+    site: &Site,
+    path: &Path,
+    raw_content: &str,
+    line_offset: usize,
+    title: Option<&str>,
+    description: Option<&str>,
+    ignored: &[LintRule],
+) -> Vec<ContentLintIssue> {
+    let lint = &site.config.content_lint;
+    let mut issues: Vec<_> = lint_markdown(raw_content, &site.config)
+        .into_iter()
+        .map(|issue| (issue.rule, line_offset + issue.line, issue.message))
+        .collect();
+
+    let lengths = [
+        (LintRule::LongTitle, "title", title, lint.max_title_length),
+        (LintRule::LongDescription, "description", description, lint.max_description_length),
+    ];
+    for (rule, key, value, max) in lengths {
+        let Some(value) = value else { continue };
+        let length = value.chars().count();
+        if length > max {
+            // Only the front matter, the lines before the content, can set the key
+            let source = read_file(path).unwrap_or_default();
+            let front_matter: Vec<_> = source.lines().take(line_offset).collect();
+            issues.push((
+                rule,
+                front_matter_line(&front_matter, key),
+                format!("The {} is {} characters long, more than {}", key, length, max),
+            ));
+        }
+    }
+
+    let relative = path.strip_prefix(&site.base_path).unwrap_or(path);
+    issues
+        .into_iter()
+        .filter(|(rule, _, _)| lint.level(*rule) != LintLevel::Off && !ignored.contains(rule))
+        .map(|(rule, line, message)| ContentLintIssue {
+            level: lint.level(rule),
+            rule,
+            path: relative.display().to_string(),
+            line,
+            message,
+        })
+        .collect()
+}
+
+/// Finds what the content lint rules report in all pages and sections, sorted by file and line
+pub fn find_content_lint_issues(site: &Site) -> Vec<ContentLintIssue> {
+    let library = site.library.read().unwrap();
+    let pages = library.pages.values().filter(|p| !p.is_fallback).flat_map(|page| {
+        lint_file(
+            site,
+            &page.file.path,
+            &page.raw_content,
+            page.content_line_offset,
+            page.meta.title.as_deref(),
+            page.meta.description.as_deref(),
+            &page.meta.lint_ignore,
+        )
+    });
+    let sections = library.sections.values().flat_map(|section| {
+        lint_file(
+            site,
+            &section.file.path,
+            &section.raw_content,
+            section.content_line_offset,
+            section.meta.title.as_deref(),
+            section.meta.description.as_deref(),
+            &section.meta.lint_ignore,
+        )
+    });
+
+    let mut issues: Vec<_> = pages.chain(sections).collect();
+    issues.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+    issues
+}
+
+/// Logs the content lint warnings and errors out if any rule set to "error" found something
+pub fn check_content(site: &Site) -> Result<()> {
+    println!("Checking content.");
+    let issues = find_content_lint_issues(site);
+    let format = |level| -> Vec<String> {
+        issues
+            .iter()
+            .filter(|issue| issue.level == level)
+            .enumerate()
+            .map(|(i, issue)| {
+                format!(
+                    "  {}. {}:{}: [{}] {}",
+                    i + 1,
+                    issue.path,
+                    issue.line,
+                    issue.rule,
+                    issue.message
+                )
+            })
+            .collect()
+    };
+
+    let warnings = format(LintLevel::Warn);
+    if !warnings.is_empty() {
+        console::warn(&format!(
+            "Found {} content lint warning(s)\n{}",
+            warnings.len(),
+            warnings.join("\n")
+        ));
+    }
+    let errors = format(LintLevel::Error);
+    if !errors.is_empty() {
+        return Err(anyhow!("Found {} content lint error(s)\n{}", errors.len(), errors.join("\n")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::front_matter_line;
+
+    #[test]
+    fn can_find_front_matter_line() {
+        let front_matter = "+++\ntitle = \"A\"\n  description=\"B\"\n+++\n";
+        assert_eq!(front_matter_line(front_matter, "title"), 2);
+        assert_eq!(front_matter_line(front_matter, "description"), 3);
+        assert_eq!(front_matter_line("---\ntitle: A\n---\n", "title"), 2);
+        assert_eq!(front_matter_line("+++\ntitles = 1\n+++\n", "title"), 1);
+    }
+}
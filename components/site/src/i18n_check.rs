@@ -0,0 +1,143 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use config::LinkCheckerLevel;
+use errors::{anyhow, Result};
+use libs::once_cell::sync::Lazy;
+use libs::regex::Regex;
+use utils::fs::read_file;
+
+use crate::Site;
+
+static TRANS_CALL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\btrans\s*\(([^)]*)\)").unwrap());
+static KEY_ARG_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\bkey\s*=\s*["'`]([^"'`]*)["'`]"#).unwrap());
+
+/// Finds the pages and sections that are not translated in every language.
+/// Pages rendered through `translation_fallback` count as missing.
+pub fn find_missing_content(site: &Site) -> Vec<String> {
+    let library = site.library.read().unwrap();
+    let config = &site.config;
+
+    // translation id -> (relative path of one of the translations, languages)
+    let mut translations: BTreeMap<PathBuf, (String, BTreeSet<&str>)> = BTreeMap::new();
+    let pages = library
+        .pages
+        .values()
+        .filter(|p| !p.is_fallback)
+        .map(|p| (p.translation_id(), &p.file.relative, p.lang.as_str()));
+    let sections =
+        library.sections.values().map(|s| (s.translation_id(), &s.file.relative, s.lang.as_str()));
+    for (id, relative, lang) in pages.chain(sections) {
+        let entry = translations.entry(id).or_insert_with(|| (relative.clone(), BTreeSet::new()));
+        // Report the file of the default language if there is one
+        if lang == config.default_language {
+            entry.0 = relative.clone();
+        }
+        entry.1.insert(lang);
+    }
+
+    let all_languages: BTreeSet<&str> = config.languages.keys().map(|l| l.as_str()).collect();
+    translations
+        .into_values()
+        .filter_map(|(relative, languages)| {
+            let missing: Vec<_> = all_languages.difference(&languages).copied().collect();
+            if missing.is_empty() {
+                None
+            } else {
+                Some(format!("`{}` is not translated in: {}", relative, missing.join(", ")))
+            }
+        })
+        .collect()
+}
+
+/// Finds the `translations` keys defined in some languages but not in others
+pub fn find_missing_keys(site: &Site) -> Vec<String> {
+    let languages = &site.config.languages;
+    let all_keys: BTreeSet<&str> =
+        languages.values().flat_map(|l| l.translations.keys()).map(|k| k.as_str()).collect();
+
+    let mut codes: Vec<_> = languages.keys().collect();
+    codes.sort();
+    codes
+        .into_iter()
+        .filter_map(|code| {
+            let missing: Vec<_> = all_keys
+                .iter()
+                .filter(|k| !languages[code].translations.contains_key(**k))
+                .map(|k| format!("`{}`", k))
+                .collect();
+            if missing.is_empty() {
+                None
+            } else {
+                Some(format!("`{}` is missing the translation keys: {}", code, missing.join(", ")))
+            }
+        })
+        .collect()
+}
+
+/// Finds the keys given to `trans` in the templates that no language defines.
+/// Only keys written as string literals can be found.
+pub fn find_undefined_keys(site: &Site) -> Result<Vec<String>> {
+    let languages = &site.config.languages;
+    let mut used_keys: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+
+    for template in site.tera.templates.values() {
+        // Skip the built-in templates
+        let Some(ref path) = template.path else { continue };
+        let source = read_file(&PathBuf::from(path))?;
+        for call in TRANS_CALL_RE.captures_iter(&source) {
+            if let Some(key) = KEY_ARG_RE.captures(&call[1]) {
+                used_keys.entry(key[1].to_string()).or_default().insert(&template.name);
+            }
+        }
+    }
+
+    Ok(used_keys
+        .into_iter()
+        .filter(|(key, _)| !languages.values().any(|l| l.translations.contains_key(key)))
+        .map(|(key, templates)| {
+            let templates: Vec<_> = templates.into_iter().collect();
+            format!("`{}` used in {} is not defined in any language", key, templates.join(", "))
+        })
+        .collect())
+}
+
+/// Logs or errors on the messages depending on the level
+fn report(level: &LinkCheckerLevel, title: &str, messages: Vec<String>) -> Result<()> {
+    if messages.is_empty() {
+        return Ok(());
+    }
+
+    let messages: Vec<String> =
+        messages.iter().enumerate().map(|(i, msg)| format!("  {}. {}", i + 1, msg)).collect();
+    let msg = format!("Found {} {}\n{}", messages.len(), title, messages.join("\n"));
+    match level {
+        LinkCheckerLevel::Warn => {
+            console::warn(&msg);
+            Ok(())
+        }
+        LinkCheckerLevel::Error => Err(anyhow!(msg)),
+    }
+}
+
+/// Reports the missing translations of the site, as configured in `[i18n_check]`
+pub fn check_translations(site: &Site) -> Result<()> {
+    println!("Checking translations.");
+    let i18n_check = &site.config.i18n_check;
+    report(
+        &i18n_check.missing_content,
+        "page(s)/section(s) with missing translations",
+        find_missing_content(site),
+    )?;
+    report(
+        &i18n_check.missing_keys,
+        "language(s) with missing translation keys",
+        find_missing_keys(site),
+    )?;
+    report(
+        &i18n_check.undefined_keys,
+        "undefined translation key(s) used in templates",
+        find_undefined_keys(site)?,
+    )
+}
@@ -1,8 +1,11 @@
+pub mod content_lint;
 pub mod feeds;
+pub mod i18n_check;
 pub mod link_checking;
 mod minify;
 pub mod sass;
 pub mod sitemap;
+pub mod spellcheck;
 pub mod tpls;
 
 use std::borrow::Cow;
@@ -16,8 +19,9 @@ use libs::rayon::prelude::*;
 use libs::tera::{Context, Tera};
 use libs::walkdir::{DirEntry, WalkDir};
 
-use config::{get_config, Config, IndexFormat};
-use content::{Library, Page, Paginator, Section, Taxonomy};
+use crate::sitemap::SitemapEntry;
+use config::{get_config, Config, Glossary, GlossaryTerm, IndexFormat};
+use content::{read_git_history, GitInfo, Library, Page, Paginator, Section, Taxonomy};
 use errors::{anyhow, bail, Result};
 use libs::relative_path::RelativePathBuf;
 use std::time::Instant;
@@ -26,8 +30,9 @@ use utils::fs::{
     clean_site_output_folder, copy_directory, copy_file_if_needed, create_directory, create_file,
 };
 use utils::net::{get_available_port, is_external_link};
+use utils::site::WikiLinkTargets;
 use utils::templates::{render_template, ShortcodeDefinition};
-use utils::types::InsertAnchor;
+use utils::types::{AutoSummary, InsertAnchor};
 
 pub static SITE_CONTENT: Lazy<Arc<RwLock<HashMap<RelativePathBuf, String>>>> =
     Lazy::new(|| Arc::new(RwLock::new(HashMap::new())));
@@ -70,6 +75,11 @@ pub struct Site {
     shortcode_definitions: HashMap<String, ShortcodeDefinition>,
     /// Whether to check external links
     check_external_links: bool,
+    /// Whether `zola check` checks the spelling of the content
+    check_spelling: bool,
+    /// The git history of the content files, keyed by their path relative to `content`.
+    /// Empty unless `use_git_history` is enabled
+    git_history: HashMap<String, GitInfo>,
 }
 
 impl Site {
@@ -114,6 +124,8 @@ impl Site {
             build_mode: BuildMode::Disk,
             shortcode_definitions,
             check_external_links: true,
+            check_spelling: false,
+            git_history: HashMap::new(),
         };
 
         Ok(site)
@@ -137,6 +149,11 @@ impl Site {
         self.check_external_links = false;
     }
 
+    /// Set the site checker to check the spelling of the content.
+    pub fn enable_spellcheck(&mut self) {
+        self.check_spelling = true;
+    }
+
     /// The index sections are ALWAYS at those paths
     /// There are one index section for the default language + 1 per language
     fn index_section_paths(&self) -> Vec<(PathBuf, Option<&str>)> {
@@ -168,6 +185,7 @@ impl Site {
 
     pub fn set_base_url(&mut self, base_url: String) {
         self.config.base_url = base_url;
+        self.config.clear_languages_base_url();
         let mut imageproc = self.imageproc.lock().expect("Couldn't lock imageproc (set_base_url)");
         imageproc.set_base_url(&self.config);
     }
@@ -184,6 +202,9 @@ impl Site {
     /// out of them
     pub fn load(&mut self) -> Result<()> {
         self.library = Arc::new(RwLock::new(Library::new(&self.config)));
+        if self.config.use_git_history {
+            self.git_history = read_git_history(&self.content_path)?;
+        }
         let mut pages_insert_anchors = HashMap::new();
 
         // not the most elegant loop, but this is necessary to use skip_current_dir
@@ -311,6 +332,14 @@ impl Site {
             );
             self.add_page(page, false)?;
         }
+        self.check_translation_keys()?;
+        for page in self.find_translation_fallbacks() {
+            pages_insert_anchors.insert(
+                page.file.path.clone(),
+                self.find_parent_section_insert_anchor(&page.file.parent.clone(), &page.lang),
+            );
+            self.add_page(page, false)?;
+        }
 
         {
             let library = self.library.read().unwrap();
@@ -328,8 +357,12 @@ impl Site {
         // taxonomy Tera fns are loaded in `register_early_global_fns`
         // so we do need to populate it first.
         self.populate_taxonomies()?;
+        self.populate_authors();
         tpls::register_early_global_fns(self)?;
         self.populate_sections();
+        self.populate_archives();
+        self.check_page_schemas()?;
+        self.load_glossaries()?;
         self.render_markdown()?;
         {
             let mut lib = self.library.write().unwrap();
@@ -379,9 +412,65 @@ impl Site {
             }
         }
 
+        if self.config.is_in_check_mode() {
+            i18n_check::check_translations(self)?;
+            content_lint::check_content(self)?;
+            if self.check_spelling {
+                spellcheck::check_spelling(self)?;
+            }
+        }
+
         Ok(())
     }
 
+    /// Errors if several pages of the same language share a `translation_key`
+    pub fn check_translation_keys(&self) -> Result<()> {
+        let library = self.library.read().unwrap();
+        let conflicts = library.find_translation_key_conflicts();
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+
+        let mut msg =
+            String::from("Found pages sharing a `translation_key` in the same language:\n");
+        for (key, paths) in conflicts {
+            msg.push_str(&format!("- `{}` from files {:?}\n", key, paths));
+        }
+        Err(anyhow!(msg))
+    }
+
+    /// Creates the pages standing in for the missing translations of the default language pages,
+    /// in the languages with `translation_fallback` enabled
+    pub fn find_translation_fallbacks(&self) -> Vec<Page> {
+        let fallback_langs: Vec<_> = self
+            .config
+            .other_languages()
+            .into_iter()
+            .filter(|(_, options)| options.translation_fallback)
+            .map(|(code, _)| code)
+            .collect();
+        if fallback_langs.is_empty() {
+            return Vec::new();
+        }
+
+        let library = self.library.read().unwrap();
+        let translated: HashSet<_> =
+            library.pages.values().map(|p| (p.translation_id(), p.lang.as_str())).collect();
+        let mut fallbacks = Vec::new();
+        for page in library.pages.values() {
+            if page.lang != self.config.default_language || !page.meta.render {
+                continue;
+            }
+            let id = page.translation_id();
+            for lang in &fallback_langs {
+                if !translated.contains(&(id.clone(), lang)) {
+                    fallbacks.push(page.as_translation_fallback(lang, &self.config));
+                }
+            }
+        }
+        fallbacks
+    }
+
     /// Insert a default index section for each language if necessary so we don't need to create
     /// a _index.md to render the index page at the root of the site
     pub fn create_default_index_sections(&mut self) -> Result<()> {
@@ -429,22 +518,38 @@ impl Site {
         Ok(())
     }
 
+    /// What `[[Title]]` links can point to, only computed if `wiki_links` is enabled
+    fn wiki_link_targets(&self) -> WikiLinkTargets {
+        if self.config.markdown.wiki_links {
+            self.library.read().unwrap().wiki_link_targets()
+        } else {
+            WikiLinkTargets::default()
+        }
+    }
+
     /// Render the markdown of all pages/sections
     /// Used in a build and in `serve` if a shortcode has changed
     pub fn render_markdown(&mut self) -> Result<()> {
         // Another silly thing needed to not borrow &self in parallel and
         // make the borrow checker happy
         let permalinks = &self.permalinks;
+        let wiki_link_targets = &self.wiki_link_targets();
+        let titles = &self.library.read().unwrap().titles();
         let tera = &self.tera;
         let config = &self.config;
 
         // This is needed in the first place because of silly borrow checker
         let mut pages_insert_anchors = HashMap::new();
+        let mut pages_auto_summary = HashMap::new();
         for (_, p) in &self.library.read().unwrap().pages {
             pages_insert_anchors.insert(
                 p.file.path.clone(),
                 self.find_parent_section_insert_anchor(&p.file.parent.clone(), &p.lang),
             );
+            pages_auto_summary.insert(
+                p.file.path.clone(),
+                self.find_parent_section_auto_summary(&p.file.parent, &p.lang),
+            );
         }
 
         let mut library = self.library.write().expect("Get lock for render_markdown");
@@ -455,11 +560,15 @@ impl Site {
             .par_iter_mut()
             .map(|page| {
                 let insert_anchor = pages_insert_anchors[&page.file.path];
+                let auto_summary = pages_auto_summary[&page.file.path];
                 page.render_markdown(
                     permalinks,
+                    wiki_link_targets,
+                    titles,
                     tera,
                     config,
                     insert_anchor,
+                    auto_summary,
                     &self.shortcode_definitions,
                 )
             })
@@ -471,7 +580,14 @@ impl Site {
             .collect::<Vec<_>>()
             .par_iter_mut()
             .map(|section| {
-                section.render_markdown(permalinks, tera, config, &self.shortcode_definitions)
+                section.render_markdown(
+                    permalinks,
+                    wiki_link_targets,
+                    titles,
+                    tera,
+                    config,
+                    &self.shortcode_definitions,
+                )
             })
             .collect::<Result<()>>()?;
 
@@ -490,16 +606,41 @@ impl Site {
                 );
             }
         }
+        if !self.config.authors.is_empty() {
+            for author in &page.meta.authors {
+                if !self.config.authors.contains_key(author) {
+                    bail!(
+                        "Page `{}` has author `{}` which is not defined in `[authors]` in config.toml",
+                        page.file.path.display(),
+                        author
+                    );
+                }
+            }
+        }
+
+        if let Some(info) = self.git_history.get(&page.file.relative) {
+            page.set_git_info(info.clone());
+        }
+        // Applied after the git history as the pattern can use the date of the page
+        if let Some(pattern) =
+            self.find_parent_section_page_permalink(&page.file.parent, &page.lang)
+        {
+            page.apply_permalink_pattern(&pattern, &self.config)?;
+        }
 
         self.permalinks.insert(page.file.relative.clone(), page.permalink.clone());
         if render_md {
             let insert_anchor =
                 self.find_parent_section_insert_anchor(&page.file.parent, &page.lang);
+            let auto_summary = self.find_parent_section_auto_summary(&page.file.parent, &page.lang);
             page.render_markdown(
                 &self.permalinks,
+                &self.wiki_link_targets(),
+                &self.library.read().unwrap().titles(),
                 &self.tera,
                 &self.config,
                 insert_anchor,
+                auto_summary,
                 &self.shortcode_definitions,
             )?;
         }
@@ -517,7 +658,9 @@ impl Site {
         let page = Page::from_file(path, &self.config, &self.base_path)?;
         self.add_page(page, true)?;
         self.populate_sections();
+        self.populate_archives();
         self.populate_taxonomies()?;
+        self.populate_authors();
         let library = self.library.read().unwrap();
         let page = library.pages.get(path).unwrap();
         self.render_page(page)
@@ -530,6 +673,8 @@ impl Site {
         if render_md {
             section.render_markdown(
                 &self.permalinks,
+                &self.wiki_link_targets(),
+                &self.library.read().unwrap().titles(),
                 &self.tera,
                 &self.config,
                 &self.shortcode_definitions,
@@ -548,6 +693,7 @@ impl Site {
         let section = Section::from_file(path, &self.config, &self.base_path)?;
         self.add_section(section, true)?;
         self.populate_sections();
+        self.populate_archives();
         let library = self.library.read().unwrap();
         let section = library.sections.get(path).unwrap();
         self.render_section(section, true)
@@ -560,20 +706,58 @@ impl Site {
         parent_path: &Path,
         lang: &str,
     ) -> InsertAnchor {
-        let parent = if lang != self.config.default_language {
-            parent_path.join(format!("_index.{}.md", lang))
-        } else {
-            parent_path.join("_index.md")
-        };
         self.library
             .read()
             .unwrap()
             .sections
-            .get(&parent)
+            .get(&self.parent_section_path(parent_path, lang))
             .and_then(|s| s.meta.insert_anchor_links)
             .unwrap_or(self.config.markdown.insert_anchor_links)
     }
 
+    /// Finds the auto_summary for the parent section of the directory at `path`.
+    /// Defaults to the global setting if no parent section found
+    pub fn find_parent_section_auto_summary(&self, parent_path: &Path, lang: &str) -> AutoSummary {
+        self.library
+            .read()
+            .unwrap()
+            .sections
+            .get(&self.parent_section_path(parent_path, lang))
+            .and_then(|s| s.meta.auto_summary)
+            .unwrap_or(self.config.markdown.auto_summary)
+    }
+
+    /// Finds the permalink pattern for the pages in the directory at `path`: the closest
+    /// section setting `page_permalink` wins.
+    /// Defaults to the global setting if no section sets it
+    pub fn find_parent_section_page_permalink(
+        &self,
+        parent_path: &Path,
+        lang: &str,
+    ) -> Option<String> {
+        let library = self.library.read().unwrap();
+        let mut current = Some(parent_path);
+        while let Some(path) = current.filter(|p| p.starts_with(&self.content_path)) {
+            if let Some(pattern) = library
+                .sections
+                .get(&self.parent_section_path(path, lang))
+                .and_then(|s| s.meta.page_permalink.as_ref())
+            {
+                return Some(pattern.clone());
+            }
+            current = path.parent();
+        }
+        self.config.page_permalink.clone()
+    }
+
+    fn parent_section_path(&self, parent_path: &Path, lang: &str) -> PathBuf {
+        if lang != self.config.default_language {
+            parent_path.join(format!("_index.{}.md", lang))
+        } else {
+            parent_path.join("_index.md")
+        }
+    }
+
     /// Find out the direct subsections of each subsection if there are some
     /// as well as the pages for each section
     pub fn populate_sections(&mut self) {
@@ -581,12 +765,92 @@ impl Site {
         library.populate_sections(&self.config, &self.content_path);
     }
 
+    /// Validates all pages against the `page_schema` of their sections and lists every
+    /// violation found at once
+    pub fn check_page_schemas(&self) -> Result<()> {
+        let library = self.library.read().unwrap();
+        let violations = library.find_schema_violations(&self.content_path);
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        let mut msg = String::from("Found pages not matching their section `page_schema`:\n");
+        for (path, errors) in violations {
+            let path = path.strip_prefix(&self.base_path).unwrap_or(&path);
+            for error in errors {
+                msg.push_str(&format!("- {}: {}\n", path.display(), error));
+            }
+        }
+        Err(anyhow!(msg))
+    }
+
+    /// Loads the glossary of every language from the file or section set in `markdown.glossary`
+    pub fn load_glossaries(&mut self) -> Result<()> {
+        let Some(source) = self.config.markdown.glossary.clone() else {
+            return Ok(());
+        };
+
+        let mut glossaries = HashMap::new();
+        if let Some(section_path) = source.strip_prefix("@/") {
+            // Each page of the section, and of its translations, is a term
+            let library = self.library.read().unwrap();
+            let Some(section) = library.sections.get(&self.content_path.join(section_path)) else {
+                bail!("The glossary section `{}` does not exist", source);
+            };
+            let translation_id = section.translation_id();
+            for section in
+                library.sections.values().filter(|s| s.translation_id() == translation_id)
+            {
+                let terms = section
+                    .pages
+                    .iter()
+                    .map(|p| &library.pages[p])
+                    .filter_map(|page| {
+                        Some(GlossaryTerm {
+                            term: page.meta.title.clone()?,
+                            title: page.meta.description.clone(),
+                            link: Some(format!("@/{}", page.file.relative)),
+                        })
+                    })
+                    .collect();
+                glossaries.insert(section.lang.clone(), Glossary::new(terms)?);
+            }
+        } else {
+            // `glossary.toml` for the default language, `glossary.{lang}.toml` for the others
+            let path = self.base_path.join(&source);
+            for lang in self.config.languages.keys() {
+                let lang_path = if *lang == self.config.default_language {
+                    path.clone()
+                } else {
+                    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("toml");
+                    path.with_extension(format!("{}.{}", lang, extension))
+                };
+                if lang_path.exists() || lang_path == path {
+                    glossaries.insert(lang.clone(), Glossary::from_file(&lang_path)?);
+                }
+            }
+        }
+
+        self.config.markdown.glossaries = glossaries;
+        Ok(())
+    }
+
     /// Find all the tags and categories if it's asked in the config
     pub fn populate_taxonomies(&mut self) -> Result<()> {
         self.taxonomies = self.library.read().unwrap().find_taxonomies(&self.config);
         Ok(())
     }
 
+    /// Find the pages of every author defined in the config
+    pub fn populate_authors(&mut self) {
+        self.library.write().unwrap().populate_authors(&self.config);
+    }
+
+    /// Groups the pages of the section set in `[archive]` by year and month
+    pub fn populate_archives(&mut self) {
+        self.library.write().unwrap().populate_archives(&self.config, &self.content_path);
+    }
+
     /// Inject live reload script tag if in live reload mode
     fn inject_livereload(&self, mut html: String) -> String {
         if let Some(port) = self.live_reload {
@@ -764,6 +1028,8 @@ impl Site {
         start = log_time(start, "Rendered sections");
         self.render_orphan_pages()?;
         start = log_time(start, "Rendered orphan pages");
+        self.render_unlisted_pages()?;
+        start = log_time(start, "Rendered unlisted pages");
         if self.config.generate_sitemap {
             self.render_sitemap()?;
             start = log_time(start, "Rendered sitemap");
@@ -799,6 +1065,10 @@ impl Site {
         }
         self.render_taxonomies()?;
         start = log_time(start, "Rendered taxonomies");
+        self.render_authors()?;
+        start = log_time(start, "Rendered authors");
+        self.render_archives()?;
+        start = log_time(start, "Rendered archives");
         // We process images at the end as we might have picked up images to process from markdown
         // or from templates
         self.process_images()?;
@@ -828,8 +1098,18 @@ impl Site {
         Ok(())
     }
 
+    /// Where the files of a language that are not tied to a path, such as its search index,
+    /// are written: in its folder if it is served at its own base URL, at the root otherwise
+    fn lang_root_path(&self, lang: &str) -> PathBuf {
+        if self.config.languages[lang].base_url.is_some() {
+            self.output_path.join(lang)
+        } else {
+            self.output_path.clone()
+        }
+    }
+
     fn index_for_lang(&self, lang: &str) -> Result<()> {
-        let path = &self.output_path.join(self.config.search.index_format.filename(lang));
+        let path = &self.lang_root_path(lang).join(self.config.search.index_format.filename(lang));
         let library = self.library.read().unwrap();
         let content = match &self.config.search.index_format {
             IndexFormat::ElasticlunrJavascript | IndexFormat::ElasticlunrJson => {
@@ -858,16 +1138,24 @@ impl Site {
         // index first
         self.index_for_lang(&self.config.default_language)?;
 
+        let mut root_paths = vec![self.output_path.clone()];
         for (code, language) in &self.config.other_languages() {
             if code != &self.config.default_language && language.build_search_index {
+                let root_path = self.lang_root_path(code);
+                create_directory(&root_path)?;
                 self.index_for_lang(code)?;
+                if !root_paths.contains(&root_path) {
+                    root_paths.push(root_path);
+                }
             }
         }
 
         match self.config.search.index_format {
             IndexFormat::ElasticlunrJavascript | IndexFormat::ElasticlunrJson => {
                 // then elasticlunr.min.js
-                create_file(&self.output_path.join("elasticlunr.min.js"), search::ELASTICLUNR_JS)?;
+                for root_path in root_paths {
+                    create_file(&root_path.join("elasticlunr.min.js"), search::ELASTICLUNR_JS)?;
+                }
             }
             _ => {}
         }
@@ -1011,33 +1299,100 @@ impl Site {
             .collect::<Result<()>>()
     }
 
+    /// Renders the profile of every author and their feeds if the language has feeds enabled
+    pub fn render_authors(&self) -> Result<()> {
+        let library = self.library.read().unwrap();
+        library
+            .authors
+            .par_iter()
+            .filter(|author| author.should_render(&self.config))
+            .map(|author| {
+                let output = author.render(&self.tera, &self.config, &library)?;
+                let content = self.inject_livereload(output);
+                let components: Vec<_> = author.path.split('/').filter(|c| !c.is_empty()).collect();
+                self.write_content(&components, "index.html", content)?;
+
+                if self.config.languages[&author.lang].generate_feeds {
+                    self.render_feeds(
+                        author.pages.iter().map(|p| &library.pages[p]).collect(),
+                        Some(&PathBuf::from(&author.path[1..])),
+                        &author.lang,
+                        |mut context: Context| {
+                            context.insert("author", &author.serialize_without_pages(&library));
+                            context
+                        },
+                    )?;
+                }
+                Ok(())
+            })
+            .collect::<Result<()>>()
+    }
+
+    /// Renders the yearly and monthly archives set in `[archive]`
+    pub fn render_archives(&self) -> Result<()> {
+        let library = self.library.read().unwrap();
+        library
+            .archives
+            .par_iter()
+            .map(|archive| {
+                let components: Vec<_> =
+                    archive.path.split('/').filter(|c| !c.is_empty()).collect();
+                if self.config.archive.as_ref().is_some_and(|a| a.paginate_by.is_some()) {
+                    return self.render_paginated(
+                        components,
+                        &Paginator::from_archive(archive, &library, &self.config),
+                    );
+                }
+
+                let output = archive.render(&self.tera, &self.config, &library)?;
+                let content = self.inject_livereload(output);
+                self.write_content(&components, "index.html", content)?;
+                Ok(())
+            })
+            .collect::<Result<()>>()
+    }
+
     /// What it says on the tin
+    /// Languages served at their own base URL get their own sitemap in their folder
     pub fn render_sitemap(&self) -> Result<()> {
         let library = self.library.read().unwrap();
-        let all_sitemap_entries =
+        let mut all_sitemap_entries =
             { sitemap::find_entries(&library, &self.taxonomies[..], &self.config) };
+
+        for (code, base_url) in self.config.languages_with_base_url() {
+            let (lang_entries, other_entries) =
+                all_sitemap_entries.into_iter().partition(|e| e.permalink.starts_with(base_url));
+            all_sitemap_entries = other_entries;
+            self.write_sitemap(&[code], lang_entries)?;
+        }
+
+        self.write_sitemap(&[], all_sitemap_entries)
+    }
+
+    fn write_sitemap(&self, components: &[&str], entries: Vec<SitemapEntry>) -> Result<()> {
         let sitemap_limit = 30000;
 
-        if all_sitemap_entries.len() < sitemap_limit {
+        if entries.len() < sitemap_limit {
             // Create single sitemap
             let mut context = Context::new();
-            context.insert("entries", &all_sitemap_entries);
+            context.insert("entries", &entries);
             let sitemap = render_template("sitemap.xml", &self.tera, context, &self.config.theme)?;
-            self.write_content(&[], "sitemap.xml", sitemap)?;
+            self.write_content(components, "sitemap.xml", sitemap)?;
             return Ok(());
         }
 
         // Create multiple sitemaps (max 30000 urls each)
         let mut sitemap_index = Vec::new();
-        for (i, chunk) in
-            all_sitemap_entries.iter().collect::<Vec<_>>().chunks(sitemap_limit).enumerate()
-        {
+        for (i, chunk) in entries.iter().collect::<Vec<_>>().chunks(sitemap_limit).enumerate() {
             let mut context = Context::new();
             context.insert("entries", &chunk);
             let sitemap = render_template("sitemap.xml", &self.tera, context, &self.config.theme)?;
             let file_name = format!("sitemap{}.xml", i + 1);
-            self.write_content(&[], &file_name, sitemap)?;
-            let mut sitemap_url = self.config.make_permalink(&file_name);
+            self.write_content(components, &file_name, sitemap)?;
+            let mut path = components.join("/");
+            path.push('/');
+            path.push_str(&file_name);
+            let mut sitemap_url = self.config.make_permalink(&path);
             sitemap_url.pop(); // Remove trailing slash
             sitemap_index.push(sitemap_url);
         }
@@ -1051,7 +1406,7 @@ impl Site {
             main_context,
             &self.config.theme,
         )?;
-        self.write_content(&[], "sitemap.xml", sitemap)?;
+        self.write_content(components, "sitemap.xml", sitemap)?;
 
         Ok(())
     }
@@ -1188,6 +1543,16 @@ impl Site {
         Ok(())
     }
 
+    /// Renders all pages that are not listed in the section they belong to
+    pub fn render_unlisted_pages(&self) -> Result<()> {
+        let library = self.library.read().unwrap();
+        for page in library.get_all_unlisted_pages() {
+            self.render_page(page)?;
+        }
+
+        Ok(())
+    }
+
     /// Renders a list of pages when the section/index is wanting pagination.
     pub fn render_paginated<'a>(
         &self,
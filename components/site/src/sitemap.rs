@@ -64,7 +64,7 @@ pub fn find_entries<'a>(
     let mut entries = HashSet::new();
 
     for p in library.pages.values() {
-        if !p.meta.render {
+        if !p.meta.render || p.meta.unlisted {
             continue;
         }
         let mut entry = SitemapEntry::new(
@@ -119,6 +119,30 @@ pub fn find_entries<'a>(
         }
     }
 
+    for author in &library.authors {
+        if author.should_render(config) {
+            entries.insert(SitemapEntry::new(Cow::Borrowed(&author.permalink), &None));
+        }
+    }
+
+    if let Some(ref archive_config) = config.archive {
+        for archive in &library.archives {
+            entries.insert(SitemapEntry::new(Cow::Borrowed(&archive.permalink), &None));
+
+            if let Some(paginate_by) = archive_config.paginate_by {
+                if !config.should_exclude_paginated_pages_in_sitemap() {
+                    let number_pagers =
+                        (archive.pages.len() as f64 / paginate_by as f64).ceil() as isize;
+                    for i in 1..=number_pagers {
+                        let permalink =
+                            format!("{}{}/{}/", archive.permalink, archive_config.paginate_path, i);
+                        entries.insert(SitemapEntry::new(Cow::Owned(permalink), &None));
+                    }
+                }
+            }
+        }
+    }
+
     let mut entries = entries.into_iter().collect::<Vec<_>>();
     entries.sort();
     entries
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use config::LinkCheckerLevel;
+use errors::{anyhow, bail, Result};
+use libs::unicode_segmentation::UnicodeSegmentation;
+use markdown::extract_prose;
+use utils::fs::read_file;
+use utils::hunspell::Dictionary;
+
+use crate::Site;
+
+/// The en_US dictionary bundled with the `bundled-dictionaries` feature, used for English when
+/// the site has none. It is licensed under the LGPL, see `dictionaries/README.md`.
+#[cfg(feature = "bundled-dictionaries")]
+const ENGLISH_AFF: &str = include_str!("dictionaries/en_US.aff");
+#[cfg(feature = "bundled-dictionaries")]
+const ENGLISH_DIC: &str = include_str!("dictionaries/en_US.dic");
+
+/// A word not found in the dictionary of the language of its page or section
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Misspelling {
+    /// The path of the file, relative to the site
+    pub path: String,
+    pub line: usize,
+    pub word: String,
+    pub lang: String,
+}
+
+/// Finds the `.aff` file of the language in the dictionaries directory: `fr.aff`, or the first
+/// regional variant such as `fr_FR.aff`
+fn find_dictionary(dir: &Path, lang: &str) -> Option<PathBuf> {
+    let exact = dir.join(format!("{}.aff", lang));
+    if exact.exists() {
+        return Some(exact);
+    }
+
+    let mut variants: Vec<_> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+            path.extension().is_some_and(|e| e == "aff")
+                && [format!("{}_", lang), format!("{}-", lang)].iter().any(|p| stem.starts_with(p))
+        })
+        .collect();
+    variants.sort();
+    variants.into_iter().next()
+}
+
+/// Loads the dictionary of each language, with the words of the project word list. The
+/// dictionaries of the site take precedence over the bundled English one, if any
+fn load_dictionaries<'a>(
+    site: &Site,
+    languages: impl Iterator<Item = &'a str>,
+) -> Result<HashMap<String, Dictionary>> {
+    let spellcheck = &site.config.spellcheck;
+    let words = match spellcheck.words {
+        Some(ref path) => read_file(&site.base_path.join(path))?,
+        None => String::new(),
+    };
+    let words: Vec<_> =
+        words.lines().map(str::trim).filter(|w| !w.is_empty() && !w.starts_with('#')).collect();
+
+    let dir = site.base_path.join(&spellcheck.dictionaries);
+    let mut dictionaries = HashMap::new();
+    for lang in languages {
+        if dictionaries.contains_key(lang) {
+            continue;
+        }
+        let mut dictionary = match find_dictionary(&dir, lang) {
+            Some(path) => Dictionary::from_file(&path)?,
+            #[cfg(feature = "bundled-dictionaries")]
+            None if lang == "en" || lang.starts_with("en-") || lang.starts_with("en_") => {
+                Dictionary::new(ENGLISH_AFF, ENGLISH_DIC)?
+            }
+            None => bail!(
+                "No Hunspell dictionary found for the language `{}` in `{}`",
+                lang,
+                spellcheck.dictionaries
+            ),
+        };
+        for word in &words {
+            dictionary.add_word(word);
+        }
+        dictionaries.insert(lang.to_string(), dictionary);
+    }
+    Ok(dictionaries)
+}
+
+/// Whether the word is spelled correctly or accepted by the page
+fn is_correct(dictionary: &Dictionary, word: &str, ignored: &[String]) -> bool {
+    // Skip numbers, versions and the like
+    if word.chars().any(|c| c.is_numeric()) {
+        return true;
+    }
+    // Abbreviations keep their final dot in dictionaries, `e.g.` being split as `e.g`
+    dictionary.check(word)
+        || dictionary.check(&format!("{}.", word))
+        || ignored.iter().any(|w| w.to_lowercase() == word.to_lowercase())
+}
+
+/// Checks the spelling of the prose of all pages and sections, with the dictionary of their language
+pub fn find_misspellings(site: &Site) -> Result<Vec<Misspelling>> {
+    let library = site.library.read().unwrap();
+    let pages = library.pages.values().filter(|p| !p.is_fallback).map(|p| {
+        (&p.file.path, &p.raw_content, p.content_line_offset, &p.lang, &p.meta.spelling_ignore)
+    });
+    let sections = library.sections.values().map(|s| {
+        (&s.file.path, &s.raw_content, s.content_line_offset, &s.lang, &s.meta.spelling_ignore)
+    });
+    let files: Vec<_> = pages.chain(sections).collect();
+    let dictionaries =
+        load_dictionaries(site, files.iter().map(|(_, _, _, lang, _)| lang.as_str()))?;
+
+    let mut misspellings = Vec::new();
+    for (path, raw_content, line_offset, lang, ignored) in files {
+        let relative = path.strip_prefix(&site.base_path).unwrap_or(path);
+        let dictionary = &dictionaries[lang];
+
+        for (line, text) in extract_prose(raw_content, &site.config) {
+            for word in text.unicode_words() {
+                if !is_correct(dictionary, word, ignored) {
+                    misspellings.push(Misspelling {
+                        path: relative.display().to_string(),
+                        line: line_offset + line,
+                        word: word.to_string(),
+                        lang: lang.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    misspellings.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+    Ok(misspellings)
+}
+
+/// Reports the misspelled words of the site, as configured in `[spellcheck]`
+pub fn check_spelling(site: &Site) -> Result<()> {
+    println!("Checking spelling.");
+    let misspellings = find_misspellings(site)?;
+    if misspellings.is_empty() {
+        return Ok(());
+    }
+
+    let messages: Vec<String> = misspellings
+        .iter()
+        .enumerate()
+        .map(|(i, m)| format!("  {}. {}:{}: `{}` ({})", i + 1, m.path, m.line, m.word, m.lang))
+        .collect();
+    let msg = format!("Found {} misspelled word(s)\n{}", messages.len(), messages.join("\n"));
+    match site.config.spellcheck.level {
+        LinkCheckerLevel::Warn => {
+            console::warn(&msg);
+            Ok(())
+        }
+        LinkCheckerLevel::Error => Err(anyhow!(msg)),
+    }
+}
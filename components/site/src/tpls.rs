@@ -86,6 +86,16 @@ pub fn register_tera_global_fns(site: &mut Site) {
             site.library.clone(),
         ),
     );
+    site.tera.register_function(
+        "get_pages",
+        global_fns::GetPages::new(
+            site.base_path.clone(),
+            &site.config.default_language,
+            Arc::clone(&language_list),
+            site.config.slugify.taxonomies,
+            site.library.clone(),
+        ),
+    );
     site.tera.register_function(
         "get_section",
         global_fns::GetSection::new(
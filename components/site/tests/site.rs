@@ -5,7 +5,7 @@ use std::env;
 use std::path::{Path, PathBuf};
 
 use common::{build_site, build_site_with_setup};
-use config::TaxonomyConfig;
+use config::{ArchiveConfig, AuthorConfig, LintLevel, LintRule, TaxonomyConfig};
 use content::Page;
 use libs::ahash::AHashMap;
 use site::sitemap;
@@ -22,7 +22,7 @@ fn can_parse_site() {
     let library = site.library.read().unwrap();
 
     // Correct number of pages (sections do not count as pages, draft are ignored)
-    assert_eq!(library.pages.len(), 36);
+    assert_eq!(library.pages.len(), 37);
     let posts_path = path.join("content").join("posts");
 
     // Make sure the page with a url doesn't have any sections
@@ -45,7 +45,7 @@ fn can_parse_site() {
 
     let posts_section = library.sections.get(&posts_path.join("_index.md")).unwrap();
     assert_eq!(posts_section.subsections.len(), 2);
-    assert_eq!(posts_section.pages.len(), 10); // 11 with 1 draft == 10
+    assert_eq!(posts_section.pages.len(), 10); // 12 with 1 draft and 1 unlisted == 10
     assert_eq!(posts_section.ancestors, vec![index_section.file.relative.clone()]);
 
     // Make sure we remove all the pwd + content from the sections
@@ -293,6 +293,224 @@ fn can_build_site_with_live_reload_and_drafts() {
     assert!(file_exists!(public, "secret_section/secret_sub_section/hello/index.html"));
 }
 
+#[test]
+fn can_build_site_with_author_profiles() {
+    let (_, _tmp_dir, public) = build_site_with_setup("test_site", |mut site| {
+        for id in ["Foo Doe", "Bar Doe", "Baz Doe", "page@example.com (Page Author)"] {
+            site.config.authors.insert(id.to_string(), AuthorConfig::default());
+        }
+        site.config.authors.get_mut("Foo Doe").unwrap().bio = Some("Writes about Rust".to_string());
+        (site, true)
+    });
+
+    assert!(file_exists!(public, "authors/foo-doe/index.html"));
+    assert!(file_contains!(public, "authors/foo-doe/index.html", "Author: Foo Doe"));
+    assert!(file_contains!(public, "authors/foo-doe/index.html", "Writes about Rust"));
+    assert!(file_contains!(
+        public,
+        "authors/foo-doe/index.html",
+        "https://replace-this-with-your-url.com/posts/tutorials/programming/rust/"
+    ));
+    assert!(file_contains!(
+        public,
+        "authors/foo-doe/index.html",
+        "[current_path](/authors/foo-doe/)"
+    ));
+    assert!(file_exists!(public, "authors/page-example-com-page-author/index.html"));
+    // Feeds are enabled in test_site
+    assert!(file_contains!(public, "authors/foo-doe/atom.xml", "<title>Rust</title>"));
+    assert!(file_contains!(
+        public,
+        "atom.xml",
+        "<uri>https://replace-this-with-your-url.com/authors/bar-doe/</uri>"
+    ));
+    assert!(file_contains!(
+        public,
+        "sitemap.xml",
+        "<loc>https://replace-this-with-your-url.com/authors/baz-doe/</loc>"
+    ));
+}
+
+#[test]
+fn can_build_site_with_unlisted_pages() {
+    let (_, _tmp_dir, public) = build_site("test_site");
+
+    // Unlisted pages are rendered at their usual path...
+    assert!(file_exists!(public, "posts/unlisted/index.html"));
+    assert!(file_contains!(public, "posts/unlisted/index.html", "A page only reachable"));
+    // ...but are not linked anywhere
+    assert!(!file_contains!(public, "posts/index.html", "posts/unlisted/"));
+    assert!(!file_contains!(public, "sitemap.xml", "posts/unlisted/"));
+    assert!(!file_contains!(public, "atom.xml", "posts/unlisted/"));
+    assert!(!file_exists!(public, "categories/unlisted/index.html"));
+}
+
+#[test]
+fn can_build_site_with_page_permalink_pattern() {
+    let (_, _tmp_dir, public) = build_site_with_setup("test_site", |mut site| {
+        site.config.page_permalink = Some("/archive/:section/:slug/".to_string());
+        (site, true)
+    });
+
+    assert!(file_exists!(public, "archive/posts/python/index.html"));
+    assert!(file_exists!(public, "archive/posts/tutorials/devops/nix/index.html"));
+    assert!(!file_exists!(public, "posts/python/index.html"));
+    // Pages setting a `path` are left alone
+    assert!(file_exists!(public, "a-fixed-url/index.html"));
+    assert!(file_contains!(
+        public,
+        "sitemap.xml",
+        "<loc>https://replace-this-with-your-url.com/archive/posts/python/</loc>"
+    ));
+}
+
+#[test]
+fn can_find_closest_section_page_permalink_pattern() {
+    let (site, _tmp_dir, _public) = build_site("test_site");
+    let content_path = site.base_path.join("content");
+    let posts_path = content_path.join("posts");
+    {
+        let mut library = site.library.write().unwrap();
+        let posts = library.sections.get_mut(&posts_path.join("_index.md")).unwrap();
+        posts.meta.page_permalink = Some("/:year/:slug/".to_string());
+    }
+
+    let nested = posts_path.join("tutorials").join("devops");
+    assert_eq!(
+        site.find_parent_section_page_permalink(&nested, "en"),
+        Some("/:year/:slug/".to_string())
+    );
+    assert_eq!(site.find_parent_section_page_permalink(&content_path, "en"), None);
+}
+
+#[test]
+fn errors_on_page_permalink_pattern_collisions() {
+    let mut path = env::current_dir().unwrap().parent().unwrap().parent().unwrap().to_path_buf();
+    path.push("test_site");
+    let config_file = path.join("config.toml");
+    let mut site = Site::new(&path, config_file).unwrap();
+    site.config.page_permalink = Some("/:slug/".to_string());
+
+    let err = site.load().unwrap_err();
+    assert!(err.to_string().contains("Found path collisions"));
+    assert!(err.to_string().contains("- `/simple/` from files"));
+}
+
+#[test]
+fn can_link_glossary_terms() {
+    let glossary_dir = tempfile::tempdir().unwrap();
+    let glossary_path = glossary_dir.path().join("glossary.toml");
+    std::fs::write(
+        &glossary_path,
+        r#"
+[[terms]]
+term = "simple"
+title = "Not complex"
+link = "@/posts/tutorials/devops/nix.md"
+
+[[terms]]
+term = "page"
+title = "A web page"
+"#,
+    )
+    .unwrap();
+
+    let (_, _tmp_dir, public) = build_site_with_setup("test_site", |mut site| {
+        site.config.markdown.glossary = Some(glossary_path.to_string_lossy().to_string());
+        (site, true)
+    });
+
+    assert!(file_contains!(
+        public,
+        "posts/tutorials/devops/docker/index.html",
+        "A <a class=\"glossary-term\" href=\"https://replace-this-with-your-url.com/posts/tutorials/devops/nix/\"><abbr title=\"Not complex\">simple</abbr></a> <abbr title=\"A web page\">page</abbr>"
+    ));
+    // Terms are not linked on the page they link to
+    assert!(file_contains!(
+        public,
+        "posts/tutorials/devops/nix/index.html",
+        "A simple <abbr title=\"A web page\">page</abbr>"
+    ));
+}
+
+#[test]
+fn can_load_glossary_from_section() {
+    let mut path = env::current_dir().unwrap().parent().unwrap().parent().unwrap().to_path_buf();
+    path.push("test_site");
+    let mut site = Site::new(&path, path.join("config.toml")).unwrap();
+    site.config.markdown.glossary = Some("@/posts/tutorials/programming/_index.md".to_string());
+    site.load().unwrap();
+
+    let glossary = &site.config.markdown.glossaries["en"];
+    let mut terms: Vec<_> =
+        glossary.terms.iter().map(|t| (t.term.as_str(), t.link.as_deref().unwrap())).collect();
+    terms.sort();
+    assert_eq!(
+        terms,
+        vec![
+            ("Python tutorial", "@/posts/tutorials/programming/python.md"),
+            ("Rust", "@/posts/tutorials/programming/rust.md"),
+        ]
+    );
+
+    site.config.markdown.glossary = Some("@/not-a-section/_index.md".to_string());
+    assert!(site.load_glossaries().is_err());
+}
+
+#[test]
+fn can_build_site_with_archives() {
+    let (_, _tmp_dir, public) = build_site_with_setup("test_site", |mut site| {
+        site.config.archive =
+            Some(ArchiveConfig { section: "posts".to_string(), ..Default::default() });
+        (site, true)
+    });
+
+    assert!(file_contains!(public, "archive/2017/index.html", "Archive: 2017 of Posts"));
+    assert!(file_contains!(
+        public,
+        "archive/2017/index.html",
+        "https://replace-this-with-your-url.com/archive/2017/03/"
+    ));
+    assert!(file_contains!(public, "archive/2017/03/index.html", "Archive: 2017/3 of Posts"));
+    assert!(file_contains!(
+        public,
+        "archive/2017/03/index.html",
+        "https://replace-this-with-your-url.com/posts/python/"
+    ));
+    assert!(!file_contains!(public, "archive/2017/03/index.html", "posts/simple/"));
+    // Pages of transparent subsections are included
+    assert!(file_exists!(public, "archive/2018/10/index.html"));
+    assert!(!file_exists!(public, "archive/2019/index.html"));
+    assert!(file_contains!(
+        public,
+        "sitemap.xml",
+        "<loc>https://replace-this-with-your-url.com/archive/2018/10/</loc>"
+    ));
+}
+
+#[test]
+fn can_build_site_with_paginated_archives() {
+    let (_, _tmp_dir, public) = build_site_with_setup("test_site", |mut site| {
+        site.config.archive = Some(ArchiveConfig {
+            section: "posts".to_string(),
+            monthly: false,
+            paginate_by: Some(2),
+            ..Default::default()
+        });
+        (site, true)
+    });
+
+    assert!(file_contains!(public, "archive/2017/index.html", "Num pagers: 3"));
+    assert!(file_exists!(public, "archive/2017/page/3/index.html"));
+    assert!(!file_exists!(public, "archive/2017/page/4/index.html"));
+    assert!(!file_exists!(public, "archive/2017/03/index.html"));
+    assert!(file_contains!(
+        public,
+        "archive/2017/page/2/index.html",
+        "[current_path](/archive/2017/page/2/)"
+    ));
+}
+
 #[test]
 fn can_build_site_with_taxonomies() {
     let (site, _tmp_dir, public) = build_site_with_setup("test_site", |mut site| {
@@ -619,8 +837,8 @@ fn can_build_site_with_pagination_for_taxonomy() {
 
             let pages_data = std::mem::replace(&mut library.pages, AHashMap::new());
             for (i, (_, mut page)) in pages_data.into_iter().enumerate() {
-                // Discard not rendered pages
-                if i % 2 == 0 && page.meta.render {
+                // Discard not rendered and unlisted pages
+                if i % 2 == 0 && page.meta.render && !page.meta.unlisted {
                     nb_a_pages += 1;
                 }
                 page.meta.taxonomies = {
@@ -942,3 +1160,74 @@ fn can_find_site_and_page_authors() {
 fn current_path(path: &str) -> String {
     format!("[current_path]({})", path)
 }
+
+#[test]
+fn can_lint_content() {
+    let (mut site, _tmp_dir, _public) = build_site("test_site");
+    site.config.content_lint.max_title_length = 40;
+    site.config.content_lint.long_title = LintLevel::Warn;
+    site.config.content_lint.missing_alt_text = LintLevel::Warn;
+    site.config.content_lint.bare_url = LintLevel::Error;
+
+    let issues: Vec<_> = site::content_lint::find_content_lint_issues(&site)
+        .into_iter()
+        .filter(|issue| issue.path.ends_with("render.md"))
+        .map(|issue| (issue.level, issue.rule, issue.line))
+        .collect();
+    // The image without alt text is ignored by the page
+    assert_eq!(
+        issues,
+        vec![(LintLevel::Warn, LintRule::LongTitle, 2), (LintLevel::Error, LintRule::BareUrl, 11),]
+    );
+
+    site.config.enable_check_mode();
+    site.skip_external_links_check();
+    let err = site.load().unwrap_err();
+    assert!(format!("{:#}", err)
+        .contains("render.md:11: [bare_url] The URL `https://www.getzola.org` is not a link"));
+}
+
+#[test]
+fn can_check_spelling() {
+    let dictionaries = tempfile::tempdir().unwrap();
+    std::fs::write(dictionaries.path().join("en_US.aff"), "SET UTF-8\n\nSFX S Y 1\nSFX S 0 s .\n")
+        .unwrap();
+    std::fs::write(dictionaries.path().join("en_US.dic"), "2\na\nsimple/S\n").unwrap();
+    let words = dictionaries.path().join("words.txt");
+    std::fs::write(&words, "# Project words\npage\n").unwrap();
+
+    let (mut site, _tmp_dir, _public) = build_site("test_site");
+    site.config.spellcheck.dictionaries = dictionaries.path().to_string_lossy().to_string();
+    let misspellings = |site: &Site| -> Vec<(usize, String)> {
+        site::spellcheck::find_misspellings(site)
+            .unwrap()
+            .into_iter()
+            .filter(|m| m.path.ends_with("devops/nix.md"))
+            .map(|m| (m.line, m.word))
+            .collect()
+    };
+    assert_eq!(misspellings(&site), vec![(7, "page".to_string())]);
+
+    site.config.spellcheck.words = Some(words.to_string_lossy().to_string());
+    assert!(misspellings(&site).is_empty());
+
+    // Without a dictionary in the site, English can only use the bundled one
+    site.config.spellcheck.dictionaries = "missing".to_string();
+    site.config.spellcheck.words = None;
+    if cfg!(feature = "bundled-dictionaries") {
+        assert!(misspellings(&site).is_empty());
+    } else {
+        let err = site::spellcheck::find_misspellings(&site).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No Hunspell dictionary found for the language `en` in `missing`"
+        );
+    }
+
+    // Other languages have no bundled dictionary
+    let (mut site, _tmp_dir, _public) = build_site("test_site_i18n");
+    site.config.spellcheck.dictionaries = "missing".to_string();
+    let err = site::spellcheck::find_misspellings(&site).unwrap_err();
+    assert!(err.to_string().starts_with("No Hunspell dictionary found for the language `"));
+    assert!(err.to_string().ends_with("` in `missing`"));
+}
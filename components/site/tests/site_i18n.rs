@@ -3,7 +3,8 @@ mod common;
 use std::env;
 
 use common::*;
-use site::Site;
+use site::{i18n_check, Site};
+use tempfile::tempdir;
 
 #[test]
 fn can_parse_multilingual_site() {
@@ -207,3 +208,106 @@ fn correct_translations_on_all_pages() {
         assert!(ensure_translations_in_output(&site, path, &link));
     }
 }
+
+#[test]
+fn can_build_multilingual_site_with_translation_fallback() {
+    let (site, _tmp_dir, public) = build_site_with_setup("test_site_i18n", |mut site| {
+        site.config.languages.get_mut("fr").unwrap().translation_fallback = true;
+        (site, true)
+    });
+
+    // The English content is rendered in French, flagged as a fallback
+    assert!(file_contains!(
+        public,
+        "fr/blog/not-translated/index.html",
+        "Something not translated"
+    ));
+    assert!(file_contains!(public, "fr/blog/not-translated/index.html", "Language: fr"));
+    assert!(file_contains!(public, "fr/blog/not-translated/index.html", "Not translated yet"));
+    assert!(file_contains!(
+        public,
+        "blog/not-translated/index.html",
+        "Translated in fr:  https://example.com/fr/blog/not-translated/"
+    ));
+    // Actual translations are not replaced
+    assert!(!file_contains!(public, "fr/blog/something/index.html", "Not translated yet"));
+    // Only for the languages asking for it
+    assert!(!file_exists!(public, "it/blog/not-translated/index.html"));
+
+    let library = site.library.read().unwrap();
+    let fr_blog = &library.sections[&site.base_path.join("content/blog/_index.fr.md")];
+    assert_eq!(fr_blog.pages.len(), 5);
+}
+
+#[test]
+fn can_build_multilingual_site_with_language_base_url() {
+    let (_, _tmp_dir, public) = build_site_with_setup("test_site_i18n", |mut site| {
+        let fr = site.config.languages.get_mut("fr").unwrap();
+        fr.base_url = Some("https://example.fr".to_string());
+        fr.build_search_index = true;
+        (site, true)
+    });
+
+    // Still written in their folder, which can be deployed on its own
+    assert!(file_exists!(public, "fr/blog/something/index.html"));
+    assert!(file_contains!(
+        public,
+        "blog/something/index.html",
+        "Translated in fr: Quelque chose https://example.fr/blog/something/"
+    ));
+    assert!(file_contains!(
+        public,
+        "fr/blog/something/index.html",
+        "Translated in en: Something https://example.com/blog/something/"
+    ));
+
+    // Feeds use the base URL of their language
+    assert!(file_contains!(public, "fr/atom.xml", "https://example.fr/blog/something-else/"));
+    assert!(file_contains!(public, "fr/atom.xml", "https://example.fr/atom.xml"));
+
+    // Each domain has its own sitemap
+    assert!(file_contains!(public, "sitemap.xml", "https://example.com/blog/something-else/"));
+    assert!(file_contains!(public, "sitemap.xml", "https://example.com/it/blog/something-else/"));
+    assert!(!file_contains!(public, "sitemap.xml", "https://example.fr/"));
+    assert!(file_contains!(public, "fr/sitemap.xml", "https://example.fr/blog/something-else/"));
+    assert!(file_contains!(public, "fr/sitemap.xml", "https://example.fr/tags/bonjour/"));
+    assert!(!file_contains!(public, "fr/sitemap.xml", "https://example.com/"));
+
+    // And its own search index
+    assert!(file_exists!(public, "fr/search_index.fr.js"));
+    assert!(file_contains!(public, "fr/search_index.fr.js", "https://example.fr/blog/something/"));
+    assert!(file_exists!(public, "fr/elasticlunr.min.js"));
+    assert!(!file_exists!(public, "search_index.fr.js"));
+}
+
+#[test]
+fn can_find_missing_translations() {
+    let mut path = env::current_dir().unwrap().parent().unwrap().parent().unwrap().to_path_buf();
+    path.push("test_site_i18n");
+    let config_file = path.join("config.toml");
+    let mut site = Site::new(&path, &config_file).unwrap();
+    let tmp_dir = tempdir().unwrap();
+    let tpl_path = tmp_dir.path().join("footer.html");
+    std::fs::write(&tpl_path, r#"{{ trans(key="title") }} {{ trans(lang=lang, key="subtitle") }}"#)
+        .unwrap();
+    site.tera.add_template_file(&tpl_path, Some("footer.html")).unwrap();
+    site.load().unwrap();
+
+    let missing_content = i18n_check::find_missing_content(&site);
+    assert!(missing_content
+        .contains(&"`blog/not-translated.md` is not translated in: fr, it".to_string()));
+    assert!(missing_content.contains(
+        &"`blog/not-translated-in-default-language.fr.md` is not translated in: en, it".to_string()
+    ));
+    assert!(missing_content.contains(&"`blog/something.md` is not translated in: it".to_string()));
+    assert!(!missing_content.iter().any(|m| m.contains("fixed-slug")));
+
+    assert_eq!(
+        i18n_check::find_missing_keys(&site),
+        vec!["`it` is missing the translation keys: `title`".to_string()]
+    );
+    assert_eq!(
+        i18n_check::find_undefined_keys(&site).unwrap(),
+        vec!["`subtitle` used in footer.html is not defined in any language".to_string()]
+    );
+}
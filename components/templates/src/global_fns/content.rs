@@ -1,5 +1,5 @@
-use content::{Library, Taxonomy, TaxonomyTerm};
-use libs::tera::{from_value, to_value, Function as TeraFn, Result, Value};
+use content::{parse_datetime, sort_pages, Library, Page, SortBy, Taxonomy, TaxonomyTerm};
+use libs::tera::{from_value, to_value, Function as TeraFn, Map, Result, Value};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -351,11 +351,223 @@ impl TeraFn for GetTaxonomyTerm {
     }
 }
 
+/// A string or a list of strings, such as `section="blog/_index.md"` or `section=["a", "b"]`
+fn string_or_list(value: Option<&Value>, err: &str) -> Result<Vec<String>> {
+    match value {
+        None => Ok(Vec::new()),
+        Some(Value::String(s)) => Ok(vec![s.clone()]),
+        Some(v) => from_value::<Vec<String>>(v.clone()).map_err(|_| err.into()),
+    }
+}
+
+/// Whether the `extra` value matches the one asked: equal to it, or to one of them if a list is
+/// asked. A list in the front matter matches if any of its items does.
+fn extra_matches(value: &Value, expected: &Value) -> bool {
+    match (value, expected) {
+        (Value::Array(items), _) => items.iter().any(|item| extra_matches(item, expected)),
+        (_, Value::Array(options)) => options.contains(value),
+        _ => value == expected,
+    }
+}
+
+/// Finds the value of a dotted key, e.g. `author.name`, in the `extra` of a page
+fn find_extra<'a>(extra: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    let mut parts = key.split('.');
+    let mut current = extra.get(parts.next()?)?;
+    for part in parts {
+        current = current.as_object()?.get(part)?;
+    }
+    Some(current)
+}
+
+#[derive(Debug)]
+pub struct GetPages {
+    base_path: PathBuf,
+    default_lang: String,
+    supported_languages: Arc<Vec<String>>,
+    taxonomies_slugify: SlugifyStrategy,
+    library: Arc<RwLock<Library>>,
+}
+impl GetPages {
+    pub fn new(
+        base_path: PathBuf,
+        default_lang: &str,
+        supported_languages: Arc<Vec<String>>,
+        taxonomies_slugify: SlugifyStrategy,
+        library: Arc<RwLock<Library>>,
+    ) -> Self {
+        Self {
+            base_path: base_path.join("content"),
+            default_lang: default_lang.to_string(),
+            supported_languages,
+            taxonomies_slugify,
+            library,
+        }
+    }
+}
+impl TeraFn for GetPages {
+    fn call(&self, args: &HashMap<String, Value>) -> Result<Value> {
+        let sections = string_or_list(
+            args.get("section"),
+            "`get_pages`: `section` must be a string or a list of strings",
+        )?;
+        let recursive = optional_arg!(
+            bool,
+            args.get("recursive"),
+            "`get_pages`: `recursive` must be a boolean (true or false)"
+        )
+        .unwrap_or(false);
+        let taxonomies = optional_arg!(
+            Map<String, Value>,
+            args.get("taxonomies"),
+            "`get_pages`: `taxonomies` must be a map of taxonomy names to terms"
+        )
+        .unwrap_or_default();
+        let lang = optional_arg!(String, args.get("lang"), "`get_pages`: `lang` must be a string")
+            .unwrap_or_else(|| self.default_lang.clone());
+        let mut dates = Vec::new();
+        for key in ["after", "before"] {
+            let date = optional_arg!(
+                String,
+                args.get(key),
+                format!("`get_pages`: `{}` must be a date string", key)
+            );
+            dates.push(match date {
+                Some(date) => Some(parse_datetime(&date).ok_or_else(|| {
+                    format!("`get_pages`: `{}` is not a valid date: `{}`", key, date)
+                })?),
+                None => None,
+            });
+        }
+        let (after, before) = (dates[0], dates[1]);
+        let draft = optional_arg!(
+            bool,
+            args.get("draft"),
+            "`get_pages`: `draft` must be a boolean (true or false)"
+        );
+        let unlisted = optional_arg!(
+            bool,
+            args.get("unlisted"),
+            "`get_pages`: `unlisted` must be a boolean (true or false)"
+        )
+        .unwrap_or(false);
+        let extra = optional_arg!(
+            Map<String, Value>,
+            args.get("extra"),
+            "`get_pages`: `extra` must be a map of keys to values"
+        )
+        .unwrap_or_default();
+        let sort_by = optional_arg!(
+            SortBy,
+            args.get("sort_by"),
+            "`get_pages`: `sort_by` must be one of `date`, `update_date`, `title`, `title_bytes`, `weight`, `slug`, `permalink` or `none`"
+        )
+        .unwrap_or(SortBy::Date);
+        let reverse = optional_arg!(
+            bool,
+            args.get("reverse"),
+            "`get_pages`: `reverse` must be a boolean (true or false)"
+        )
+        .unwrap_or(false);
+        let limit =
+            optional_arg!(usize, args.get("limit"), "`get_pages`: `limit` must be a number");
+
+        let library = self.library.read().unwrap();
+        // The sections are given like in `get_section`, we compare them with the page ancestors
+        let mut section_paths = Vec::with_capacity(sections.len());
+        for section in &sections {
+            let path = get_path_with_lang(
+                section,
+                &Some(lang.clone()),
+                &self.default_lang,
+                &self.supported_languages,
+            )?;
+            match library.sections.get(&self.base_path.join(path.as_ref())) {
+                Some(s) => section_paths.push(s.file.relative.clone()),
+                None => {
+                    return Err(format!(
+                        "`get_pages`: section `{}` not found for language `{}`.",
+                        section, lang
+                    )
+                    .into())
+                }
+            }
+        }
+
+        let mut terms = Vec::with_capacity(taxonomies.len());
+        for (name, value) in &taxonomies {
+            let wanted = string_or_list(
+                Some(value),
+                "`get_pages`: the terms of `taxonomies` must be a string or a list of strings",
+            )?;
+            let wanted: Vec<_> =
+                wanted.iter().map(|t| slugify_paths(t, self.taxonomies_slugify)).collect();
+            terms.push((name, wanted));
+        }
+
+        let matches = |page: &Page| -> bool {
+            let in_section = section_paths.is_empty()
+                || section_paths.iter().any(|section| {
+                    if recursive {
+                        page.ancestors.contains(section)
+                    } else {
+                        page.ancestors.last() == Some(section)
+                    }
+                });
+            let has_terms = terms.iter().all(|(name, wanted)| {
+                page.meta.taxonomies.get(name.as_str()).is_some_and(|page_terms| {
+                    page_terms
+                        .iter()
+                        .any(|t| wanted.contains(&slugify_paths(t, self.taxonomies_slugify)))
+                })
+            });
+            let in_dates = (after.is_none() && before.is_none())
+                || page.meta.datetime.is_some_and(|date| {
+                    after.is_none_or(|after| date > after)
+                        && before.is_none_or(|before| date < before)
+                });
+            let has_extra = extra.iter().all(|(key, expected)| {
+                find_extra(&page.meta.extra, key).is_some_and(|v| extra_matches(v, expected))
+            });
+
+            page.meta.render
+                && page.lang == lang
+                && page.meta.unlisted == unlisted
+                && draft.is_none_or(|draft| page.meta.draft == draft)
+                && in_section
+                && has_terms
+                && in_dates
+                && has_extra
+        };
+
+        let mut pages: Vec<&Page> = library.pages.values().filter(|p| matches(p)).collect();
+        // Sorting by permalink first so pages that cannot be sorted keep a stable order
+        pages.sort_by(|a, b| a.permalink.cmp(&b.permalink));
+        let (mut paths, unsorted) = match sort_by {
+            SortBy::None => (pages.iter().map(|p| p.file.path.clone()).collect(), Vec::new()),
+            _ => sort_pages(&pages, sort_by),
+        };
+        if reverse {
+            paths.reverse();
+        }
+        // The pages that cannot be sorted come last in both orders
+        paths.extend(unsorted);
+        if let Some(limit) = limit {
+            paths.truncate(limit);
+        }
+
+        let pages: Vec<_> =
+            paths.iter().map(|p| library.pages[p].serialize_without_siblings(&library)).collect();
+        Ok(to_value(pages).unwrap())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use config::{Config, TaxonomyConfig};
     use content::{FileInfo, Library, Page, Section, SortBy, TaxonomyTerm};
+    use libs::serde_json::json;
     use std::path::Path;
     use std::sync::{Arc, RwLock};
 
@@ -699,4 +911,88 @@ mod tests {
         args.insert("kind".to_string(), to_value("something-else").unwrap());
         assert!(static_fn.call(&args).is_err());
     }
+
+    #[test]
+    fn can_get_pages() {
+        let mut config = Config::default_for_test();
+        config.languages.get_mut("en").unwrap().taxonomies =
+            vec![TaxonomyConfig { name: "tags".to_string(), ..TaxonomyConfig::default() }];
+        config.slugify_taxonomies();
+        let mut library = Library::new(&config);
+        for (title, path) in [
+            ("Home", "content/_index.md"),
+            ("Blog", "content/blog/_index.md"),
+            ("Rust", "content/blog/rust/_index.md"),
+            ("News", "content/news/_index.md"),
+        ] {
+            library.insert_section(create_section(title, path, "en"));
+        }
+        let pages = [
+            ("Intro", "content/blog/intro.md", "2024-01-01", "rust", false),
+            ("Traits", "content/blog/rust/traits.md", "2024-03-01", "Rust", true),
+            ("Release", "content/news/release.md", "2024-02-01", "rust", false),
+            ("Python", "content/news/python.md", "2024-04-01", "python", true),
+            ("Draft", "content/news/draft.md", "2024-05-01", "rust", false),
+        ];
+        for (title, path, date, tag, featured) in pages {
+            let mut page = create_page(title, path, "en");
+            page.meta.date = Some(date.to_string());
+            page.meta.date_to_datetime();
+            page.meta.draft = title == "Draft";
+            page.meta.weight = match title {
+                "Release" => Some(1),
+                "Python" => Some(2),
+                _ => None,
+            };
+            page.meta.taxonomies.insert("tags".to_string(), vec![tag.to_string()]);
+            page.meta.extra.insert("featured".to_string(), Value::Bool(featured));
+            library.insert_page(page);
+        }
+        library.populate_sections(&config, Path::new("/test/base/path/content"));
+
+        let static_fn = GetPages::new(
+            "/test/base/path".into(),
+            "en",
+            Arc::new(vec!["en".to_string()]),
+            SlugifyStrategy::On,
+            Arc::new(RwLock::new(library)),
+        );
+        let titles = |args: Value| -> Vec<String> {
+            let args: HashMap<String, Value> = from_value(args).unwrap();
+            let res = static_fn.call(&args).unwrap();
+            res.as_array().unwrap().iter().map(|p| p["title"].as_str().unwrap().into()).collect()
+        };
+
+        assert_eq!(titles(json!({})), vec!["Draft", "Python", "Traits", "Release", "Intro"]);
+        assert_eq!(titles(json!({"section": "blog/_index.md"})), vec!["Intro"]);
+        assert_eq!(
+            titles(json!({
+                "section": ["blog/_index.md", "news/_index.md"],
+                "recursive": true,
+                "taxonomies": {"tags": "rust"},
+                "draft": false,
+                "limit": 2,
+            })),
+            vec!["Traits", "Release"]
+        );
+        assert_eq!(
+            titles(json!({"after": "2024-01-01", "before": "2024-05-01"})),
+            vec!["Python", "Traits", "Release"]
+        );
+        assert_eq!(
+            titles(json!({"extra": {"featured": false}, "sort_by": "title", "reverse": true})),
+            vec!["Release", "Intro", "Draft"]
+        );
+        assert_eq!(
+            titles(json!({"section": "news/_index.md", "sort_by": "weight", "reverse": true})),
+            vec!["Python", "Release", "Draft"]
+        );
+
+        let mut args = HashMap::new();
+        args.insert("section".to_string(), to_value("missing/_index.md").unwrap());
+        assert_eq!(
+            static_fn.call(&args).unwrap_err().to_string(),
+            "`get_pages`: section `missing/_index.md` not found for language `en`."
+        );
+    }
 }
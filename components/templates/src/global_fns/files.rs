@@ -437,6 +437,21 @@ title = "A title"
         );
     }
 
+    #[test]
+    fn can_get_url_with_language_base_url() {
+        let mut config = Config::parse(CONFIG_DATA).unwrap();
+        config.languages.get_mut("en").unwrap().base_url = Some("https://example.com".to_string());
+        let dir = create_temp_dir();
+        let static_fn =
+            GetUrl::new(dir.path().to_path_buf(), config, HashMap::new(), PathBuf::new());
+        let mut args = HashMap::new();
+        args.insert("path".to_string(), to_value("a_section").unwrap());
+        args.insert("lang".to_string(), to_value("en").unwrap());
+        assert_eq!(static_fn.call(&args).unwrap(), "https://example.com/a_section");
+        args.insert("lang".to_string(), to_value("fr").unwrap());
+        assert_eq!(static_fn.call(&args).unwrap(), "https://remplace-par-ton-url.fr/a_section");
+    }
+
     #[test]
     fn does_not_duplicate_lang() {
         let config = Config::parse(CONFIG_DATA).unwrap();
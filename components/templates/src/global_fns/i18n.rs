@@ -17,10 +17,19 @@ impl TeraFn for Trans {
         let key = required_arg!(String, args.get("key"), "`trans` requires a `key` argument.");
         let lang = optional_arg!(String, args.get("lang"), "`trans`: `lang` must be a string.")
             .unwrap_or_else(|| self.config.default_language.clone());
+        // Every other argument can be used in the message, `count` also picking the plural form
+        let message_args = args
+            .iter()
+            .filter(|(k, _)| *k != "key" && *k != "lang")
+            .map(|(k, v)| match v {
+                Value::String(s) => (k.clone(), s.clone()),
+                _ => (k.clone(), v.to_string()),
+            })
+            .collect();
 
         let term = self
             .config
-            .get_translation(&lang, &key)
+            .get_translation_with_args(&lang, &key, &message_args)
             .map_err(|e| Error::chain("Failed to retrieve term translation", e))?;
 
         Ok(to_value(term).unwrap())
@@ -37,11 +46,18 @@ default_language = "fr"
 
 [translations]
 title = "Un titre"
+n_posts = { one = "{count} article", other = "{count} articles" }
+greeting = "Bonjour {name} !"
 
 [languages]
 [languages.en]
 [languages.en.translations]
-title = "A title" "#;
+title = "A title"
+
+[languages.pl]
+[languages.pl.translations]
+n_posts = { one = "{count} wpis", few = "{count} wpisy", many = "{count} wpisów", other = "{count} wpisu" }
+"#;
 
     #[test]
     fn can_translate_a_string() {
@@ -59,6 +75,43 @@ title = "A title" "#;
         assert_eq!(static_fn.call(&args).unwrap(), "Un titre");
     }
 
+    #[test]
+    fn can_translate_with_plural_forms() {
+        let config = Config::parse(TRANS_CONFIG).unwrap();
+        let static_fn = Trans::new(config);
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), to_value("n_posts").unwrap());
+
+        args.insert("count".to_string(), to_value(1).unwrap());
+        assert_eq!(static_fn.call(&args).unwrap(), "1 article");
+        args.insert("count".to_string(), to_value(5).unwrap());
+        assert_eq!(static_fn.call(&args).unwrap(), "5 articles");
+
+        args.insert("lang".to_string(), to_value("pl").unwrap());
+        assert_eq!(static_fn.call(&args).unwrap(), "5 wpisów");
+        args.insert("count".to_string(), to_value(24).unwrap());
+        assert_eq!(static_fn.call(&args).unwrap(), "24 wpisy");
+    }
+
+    #[test]
+    fn can_interpolate_arguments() {
+        let config = Config::parse(TRANS_CONFIG).unwrap();
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), to_value("greeting").unwrap());
+        args.insert("name".to_string(), to_value("Zola").unwrap());
+        assert_eq!(Trans::new(config).call(&args).unwrap(), "Bonjour Zola !");
+    }
+
+    #[test]
+    fn error_on_plural_forms_without_count() {
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), to_value("n_posts").unwrap());
+
+        let config = Config::parse(TRANS_CONFIG).unwrap();
+        let error = Trans::new(config).call(&args).unwrap_err();
+        assert_eq!("Failed to retrieve term translation", format!("{}", error));
+    }
+
     #[test]
     fn error_on_absent_translation_lang() {
         let mut args = HashMap::new();
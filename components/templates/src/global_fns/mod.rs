@@ -8,7 +8,9 @@ mod i18n;
 mod images;
 mod load_data;
 
-pub use self::content::{GetPage, GetSection, GetTaxonomy, GetTaxonomyTerm, GetTaxonomyUrl};
+pub use self::content::{
+    GetPage, GetPages, GetSection, GetTaxonomy, GetTaxonomyTerm, GetTaxonomyUrl,
+};
 pub use self::files::{GetHash, GetUrl};
 pub use self::i18n::Trans;
 pub use self::images::{GetImageMetadata, ResizeImage};
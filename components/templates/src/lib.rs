@@ -24,6 +24,7 @@ pub static ZOLA_TERA: Lazy<Tera> = Lazy::new(|| {
         ),
         ("__zola_builtins/anchor-link.html", include_str!("builtins/anchor-link.html")),
         ("__zola_builtins/summary-cutoff.html", include_str!("builtins/summary-cutoff.html")),
+        ("__zola_builtins/sidenote.html", include_str!("builtins/sidenote.html")),
         ("internal/alias.html", include_str!("builtins/internal/alias.html")),
     ])
     .unwrap();
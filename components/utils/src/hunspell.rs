@@ -0,0 +1,416 @@
+//! A reader for Hunspell dictionaries, the `.aff` and `.dic` files used by LibreOffice, Firefox and
+//! most spellcheckers. The words of the dictionary are expanded with their prefixes and suffixes
+//! when loading it. Compounding, suggestions and morphological data are not supported.
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use errors::{bail, Context, Result};
+
+type Flag = u32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FlagType {
+    /// One ASCII or UTF-8 character per flag, the default
+    Char,
+    /// Two characters per flag
+    Long,
+    /// Numbers separated by commas
+    Numeric,
+}
+
+impl FlagType {
+    fn parse(self, flags: &str) -> Vec<Flag> {
+        match self {
+            FlagType::Char => flags.chars().map(|c| c as Flag).collect(),
+            FlagType::Long => flags
+                .chars()
+                .collect::<Vec<_>>()
+                .chunks(2)
+                .map(|c| c.iter().fold(0, |flag, &c| (flag << 16) | c as Flag))
+                .collect(),
+            FlagType::Numeric => flags.split(',').filter_map(|f| f.trim().parse().ok()).collect(),
+        }
+    }
+}
+
+/// A character of an affix condition
+#[derive(Debug, PartialEq, Eq)]
+enum ConditionChar {
+    Any,
+    OneOf(Vec<char>),
+    NoneOf(Vec<char>),
+}
+
+impl ConditionChar {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            ConditionChar::Any => true,
+            ConditionChar::OneOf(chars) => chars.contains(&c),
+            ConditionChar::NoneOf(chars) => !chars.contains(&c),
+        }
+    }
+}
+
+fn parse_condition(condition: &str) -> Vec<ConditionChar> {
+    let mut parts = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => parts.push(ConditionChar::Any),
+            '[' => {
+                let class: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                match class.strip_prefix('^') {
+                    Some(negated) => parts.push(ConditionChar::NoneOf(negated.chars().collect())),
+                    None => parts.push(ConditionChar::OneOf(class.chars().collect())),
+                }
+            }
+            c => parts.push(ConditionChar::OneOf(vec![c])),
+        }
+    }
+    parts
+}
+
+#[derive(Debug)]
+struct AffixRule {
+    strip: String,
+    add: String,
+    /// The flags of the affixes that can be added after this one
+    continuation: Vec<Flag>,
+    condition: Vec<ConditionChar>,
+}
+
+impl AffixRule {
+    fn apply_suffix(&self, stem: &str) -> Option<String> {
+        let chars: Vec<char> = stem.chars().collect();
+        if chars.len() < self.condition.len()
+            || !chars[chars.len() - self.condition.len()..]
+                .iter()
+                .zip(&self.condition)
+                .all(|(&c, condition)| condition.matches(c))
+        {
+            return None;
+        }
+        let base = stem.strip_suffix(self.strip.as_str())?;
+        Some(format!("{}{}", base, self.add))
+    }
+
+    fn apply_prefix(&self, stem: &str) -> Option<String> {
+        let chars: Vec<char> = stem.chars().collect();
+        if chars.len() < self.condition.len()
+            || !chars.iter().zip(&self.condition).all(|(&c, condition)| condition.matches(c))
+        {
+            return None;
+        }
+        let base = stem.strip_prefix(self.strip.as_str())?;
+        Some(format!("{}{}", self.add, base))
+    }
+}
+
+#[derive(Debug)]
+struct Affix {
+    cross_product: bool,
+    rules: Vec<AffixRule>,
+}
+
+/// The settings of a `.aff` file
+struct AffixFile {
+    flag_type: FlagType,
+    prefixes: HashMap<Flag, Affix>,
+    suffixes: HashMap<Flag, Affix>,
+    need_affix: Option<Flag>,
+    forbidden: Option<Flag>,
+    only_in_compound: Option<Flag>,
+    ignore: Vec<char>,
+}
+
+impl AffixFile {
+    fn parse(aff: &str) -> Result<AffixFile> {
+        let mut file = AffixFile {
+            flag_type: FlagType::Char,
+            prefixes: HashMap::new(),
+            suffixes: HashMap::new(),
+            need_affix: None,
+            forbidden: None,
+            only_in_compound: None,
+            ignore: Vec::new(),
+        };
+
+        for (i, line) in aff.lines().enumerate() {
+            let tokens: Vec<_> = line.split_whitespace().collect();
+            let single_flag = |file: &AffixFile| {
+                tokens.get(1).and_then(|flags| file.flag_type.parse(flags).first().copied())
+            };
+            match tokens.first().copied() {
+                Some("FLAG") => {
+                    file.flag_type = match tokens.get(1).copied() {
+                        Some("long") => FlagType::Long,
+                        Some("num") => FlagType::Numeric,
+                        _ => FlagType::Char,
+                    }
+                }
+                Some("NEEDAFFIX") => file.need_affix = single_flag(&file),
+                Some("FORBIDDENWORD") => file.forbidden = single_flag(&file),
+                Some("ONLYINCOMPOUND") => file.only_in_compound = single_flag(&file),
+                Some("IGNORE") => file.ignore = tokens.get(1).unwrap_or(&"").chars().collect(),
+                Some(kind @ ("PFX" | "SFX")) => {
+                    let Some(flag) = single_flag(&file) else {
+                        bail!("Missing affix flag on line {}", i + 1);
+                    };
+                    let flag_type = file.flag_type;
+                    let affixes =
+                        if kind == "PFX" { &mut file.prefixes } else { &mut file.suffixes };
+                    // The first line of an affix is its header: `SFX flag cross_product count`
+                    let Some(affix) = affixes.get_mut(&flag) else {
+                        let cross_product = tokens.get(2) == Some(&"Y");
+                        affixes.insert(flag, Affix { cross_product, rules: Vec::new() });
+                        continue;
+                    };
+                    if tokens.len() < 4 {
+                        bail!("Invalid affix rule on line {}", i + 1);
+                    }
+                    let none = |s: &str| if s == "0" { String::new() } else { s.to_string() };
+                    let (add, continuation) = match tokens[3].split_once('/') {
+                        Some((add, flags)) => (none(add), flag_type.parse(flags)),
+                        None => (none(tokens[3]), Vec::new()),
+                    };
+                    affix.rules.push(AffixRule {
+                        strip: none(tokens[2]),
+                        add,
+                        continuation,
+                        condition: parse_condition(tokens.get(4).unwrap_or(&".")),
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        Ok(file)
+    }
+}
+
+/// Decodes a dictionary file in the encoding set by the `SET` line of the `.aff` file
+fn decode(bytes: Vec<u8>, encoding: &str) -> Result<String> {
+    match encoding.to_uppercase().as_str() {
+        "UTF-8" | "UTF8" => Ok(String::from_utf8(bytes)?),
+        // The first 256 code points of Unicode are the ones of Latin-1
+        "ISO8859-1" | "ISO-8859-1" | "LATIN1" => Ok(bytes.into_iter().map(char::from).collect()),
+        _ => bail!("Unsupported dictionary encoding `{}`, convert it to UTF-8", encoding),
+    }
+}
+
+/// The words of a language, with all their forms
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    words: HashSet<String>,
+    forbidden: HashSet<String>,
+    /// Characters removed from the words before checking them
+    ignore: Vec<char>,
+}
+
+impl Dictionary {
+    /// Loads the dictionary from the content of its `.aff` and `.dic` files
+    pub fn new(aff: &str, dic: &str) -> Result<Dictionary> {
+        let aff = AffixFile::parse(aff)?;
+        let mut dictionary = Dictionary { ignore: aff.ignore.clone(), ..Default::default() };
+
+        let mut lines = dic.lines();
+        // The first line is the number of words
+        if let Some(first) = lines.next() {
+            if first.trim().parse::<usize>().is_err() {
+                bail!("The first line of a `.dic` file should be its number of words");
+            }
+        }
+        for line in lines {
+            let Some(entry) = line.split_whitespace().next() else { continue };
+            // Slashes in words are escaped
+            let separator = entry
+                .char_indices()
+                .find(|&(i, c)| c == '/' && !entry[..i].ends_with('\\'))
+                .map(|(i, _)| i);
+            let (word, flags) = match separator {
+                Some(i) => (&entry[..i], aff.flag_type.parse(&entry[i + 1..])),
+                None => (entry, Vec::new()),
+            };
+            dictionary.expand(&aff, &word.replace("\\/", "/"), &flags);
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Loads the `.aff` file at `path` and the `.dic` file next to it
+    pub fn from_file(aff_path: &Path) -> Result<Dictionary> {
+        let dic_path = aff_path.with_extension("dic");
+        let read = |path: &Path| {
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))
+        };
+        let aff_bytes = read(aff_path)?;
+        // The encoding is given by an ASCII line of the `.aff` file
+        let encoding = String::from_utf8_lossy(&aff_bytes)
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("SET ").map(|e| e.trim().to_string()))
+            .unwrap_or_else(|| "ISO8859-1".to_string());
+
+        let aff = decode(aff_bytes, &encoding)?;
+        let dic = decode(read(&dic_path)?, &encoding)?;
+        Dictionary::new(&aff, &dic)
+            .with_context(|| format!("Invalid dictionary {}", dic_path.display()))
+    }
+
+    /// Adds the forms of a word of the dictionary
+    fn expand(&mut self, aff: &AffixFile, stem: &str, flags: &[Flag]) {
+        let has = |flag: Option<Flag>| flag.is_some_and(|f| flags.contains(&f));
+        if has(aff.forbidden) {
+            self.forbidden.insert(stem.to_string());
+            return;
+        }
+        if !has(aff.need_affix) && !has(aff.only_in_compound) {
+            self.words.insert(stem.to_string());
+        }
+
+        let needs_more =
+            |rule: &AffixRule| aff.need_affix.is_some_and(|f| rule.continuation.contains(&f));
+        // The suffixed forms that can also get a prefix
+        let mut cross_product_forms = Vec::new();
+        for affix in flags.iter().filter_map(|f| aff.suffixes.get(f)) {
+            for rule in &affix.rules {
+                let Some(form) = rule.apply_suffix(stem) else { continue };
+                for second in rule.continuation.iter().filter_map(|f| aff.suffixes.get(f)) {
+                    for second_rule in &second.rules {
+                        if let Some(form) = second_rule.apply_suffix(&form) {
+                            self.words.insert(form);
+                        }
+                    }
+                }
+                if affix.cross_product {
+                    cross_product_forms.push(form.clone());
+                }
+                if !needs_more(rule) {
+                    self.words.insert(form);
+                }
+            }
+        }
+
+        for affix in flags.iter().filter_map(|f| aff.prefixes.get(f)) {
+            let stems = std::iter::once(stem).chain(
+                cross_product_forms.iter().map(|f| f.as_str()).filter(|_| affix.cross_product),
+            );
+            for stem in stems {
+                for rule in &affix.rules {
+                    if let Some(form) = rule.apply_prefix(stem) {
+                        if !needs_more(rule) {
+                            self.words.insert(form);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Adds a word accepted as is
+    pub fn add_word(&mut self, word: &str) {
+        self.words.insert(word.to_string());
+    }
+
+    /// Whether the word is spelled correctly. Words can be capitalised or in uppercase,
+    /// `Cat` and `CAT` being correct if `cat` is.
+    pub fn check(&self, word: &str) -> bool {
+        let word: String = word
+            .chars()
+            .filter(|c| !self.ignore.contains(c))
+            .map(|c| if c == '’' { '\'' } else { c })
+            .collect();
+        if self.forbidden.contains(&word) {
+            return false;
+        }
+        if self.words.contains(&word) {
+            return true;
+        }
+
+        let lowercase = word.to_lowercase();
+        let mut chars = lowercase.chars();
+        let capitalized: String = match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => return false,
+        };
+        if word == capitalized {
+            return self.words.contains(&lowercase);
+        }
+        if word == word.to_uppercase() {
+            return self.words.contains(&lowercase) || self.words.contains(&capitalized);
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = r#"
+SET UTF-8
+# Plurals
+SFX S Y 4
+SFX S   y     ies        [^aeiou]y
+SFX S   0     s          [aeiou]y
+SFX S   0     es         [sxzh]
+SFX S   0     s          [^sxzhy]
+
+PFX U Y 1
+PFX U   0     un         .
+
+SFX D N 1
+SFX D   0     ed/S       .
+
+NEEDAFFIX X
+FORBIDDENWORD !
+"#;
+
+    const DIC: &str = "8
+cat/S
+fly/S
+kiss/SU
+happy/U
+Paris
+colour/X
+colours/!
+pa\\/s
+";
+
+    #[test]
+    fn can_expand_affixes() {
+        let dictionary = Dictionary::new(AFF, DIC).unwrap();
+        for word in ["cat", "cats", "flies", "kisses", "unkisses", "unhappy", "Paris", "pa/s"] {
+            assert!(dictionary.check(word), "{}", word);
+        }
+        for word in ["flys", "happys", "unfly", "paris", "colour", "colours"] {
+            assert!(!dictionary.check(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn can_check_case_variants() {
+        let dictionary = Dictionary::new(AFF, DIC).unwrap();
+        for word in ["Cats", "CATS", "PARIS"] {
+            assert!(dictionary.check(word), "{}", word);
+        }
+        assert!(!dictionary.check("cAts"));
+    }
+
+    #[test]
+    fn can_parse_long_and_numeric_flags() {
+        let aff = "FLAG long\nSFX Aa Y 1\nSFX Aa 0 s .\n";
+        let dictionary = Dictionary::new(aff, "1\ndog/AaBb\n").unwrap();
+        assert!(dictionary.check("dogs"));
+
+        let aff = "FLAG num\nSFX 12 Y 1\nSFX 12 0 s .\n";
+        let dictionary = Dictionary::new(aff, "1\ndog/3,12\n").unwrap();
+        assert!(dictionary.check("dogs"));
+    }
+
+    #[test]
+    fn can_decode_latin1() {
+        let bytes = vec![b'c', 0xe9];
+        assert_eq!(decode(bytes, "ISO8859-1").unwrap(), "cé");
+        assert!(decode(Vec::new(), "KOI8-R").is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use libs::percent_encoding::percent_decode;
 use std::collections::HashMap;
 
-use errors::{anyhow, Result};
+use errors::{anyhow, bail, Result};
+
+use crate::slugs::{slugify_anchors, SlugifyStrategy};
 
 /// Result of a successful resolution of an internal link.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -42,11 +44,115 @@ pub fn resolve_internal_link(
     }
 }
 
+/// The pages and sections that wiki links (`[[Title]]`) can point to, by language and by name.
+/// Names are matched case-insensitively.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WikiLinkTargets {
+    /// (lang, lowercased name) -> internal paths of the .md files with that name
+    targets: HashMap<(String, String), Vec<String>>,
+}
+
+impl WikiLinkTargets {
+    /// Makes the .md file at `md_path` the target of wiki links to any of `names` in that language
+    pub fn insert(&mut self, lang: &str, names: &[&str], md_path: &str) {
+        for name in names {
+            let name = name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            let paths = self.targets.entry((lang.to_string(), name.to_lowercase())).or_default();
+            if !paths.iter().any(|p| p == md_path) {
+                paths.push(md_path.to_string());
+            }
+        }
+    }
+
+    /// Resolves a wiki link target (of the `Title#Some heading` sort) in the given language.
+    /// The heading is slugified like the anchors of the headings.
+    /// Errors if no page or section or more than one have that name.
+    pub fn resolve(
+        &self,
+        target: &str,
+        lang: &str,
+        permalinks: &HashMap<String, String>,
+        anchor_strategy: SlugifyStrategy,
+    ) -> Result<ResolvedInternalLink> {
+        let (name, heading) = match target.split_once('#') {
+            Some((name, heading)) => (name, Some(heading)),
+            None => (target, None),
+        };
+        let md_path = match self.targets.get(&(lang.to_string(), name.trim().to_lowercase())) {
+            Some(paths) if paths.len() == 1 => &paths[0],
+            Some(paths) => {
+                let mut paths = paths.clone();
+                paths.sort();
+                bail!("Wiki link `{}` is ambiguous, it could be any of: {}", name, paths.join(", "))
+            }
+            None => bail!("Wiki link `{}` does not match any page or section", name),
+        };
+        let target = permalinks
+            .get(md_path)
+            .ok_or_else(|| anyhow!("Wiki link `{}` does not match any page or section", name))?;
+
+        let anchor = heading.map(|h| slugify_anchors(h.trim(), anchor_strategy));
+        Ok(ResolvedInternalLink {
+            permalink: match anchor {
+                Some(ref a) => format!("{}#{}", target, a),
+                None => target.to_string(),
+            },
+            md_path: md_path.to_string(),
+            anchor,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use super::resolve_internal_link;
+    use super::{resolve_internal_link, WikiLinkTargets};
+    use crate::slugs::SlugifyStrategy;
+
+    fn wiki_link_targets() -> (WikiLinkTargets, HashMap<String, String>) {
+        let mut targets = WikiLinkTargets::default();
+        targets.insert("en", &["About me", "about"], "pages/about.md");
+        targets.insert("fr", &["À propos", "about"], "pages/about.fr.md");
+        targets.insert("en", &["Setup", "setup"], "docs/setup.md");
+        targets.insert("en", &["Setup", "install"], "guides/setup.md");
+        let mut permalinks = HashMap::new();
+        permalinks.insert("pages/about.md".to_string(), "https://vincent.is/about/".to_string());
+        permalinks
+            .insert("pages/about.fr.md".to_string(), "https://vincent.is/fr/about/".to_string());
+        (targets, permalinks)
+    }
+
+    #[test]
+    fn can_resolve_wiki_links() {
+        let (targets, permalinks) = wiki_link_targets();
+        let res = targets.resolve("about ME", "en", &permalinks, SlugifyStrategy::On).unwrap();
+        assert_eq!(res.permalink, "https://vincent.is/about/");
+        assert_eq!(res.md_path, "pages/about.md");
+
+        let res = targets.resolve("about", "fr", &permalinks, SlugifyStrategy::On).unwrap();
+        assert_eq!(res.permalink, "https://vincent.is/fr/about/");
+
+        let res =
+            targets.resolve("About me#My Story", "en", &permalinks, SlugifyStrategy::On).unwrap();
+        assert_eq!(res.permalink, "https://vincent.is/about/#my-story");
+        assert_eq!(res.anchor, Some("my-story".to_string()));
+    }
+
+    #[test]
+    fn errors_on_missing_or_ambiguous_wiki_links() {
+        let (targets, permalinks) = wiki_link_targets();
+        let err = targets.resolve("Setup", "en", &permalinks, SlugifyStrategy::On).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Wiki link `Setup` is ambiguous, it could be any of: docs/setup.md, guides/setup.md"
+        );
+        let err = targets.resolve("À propos", "en", &permalinks, SlugifyStrategy::On).unwrap_err();
+        assert_eq!(err.to_string(), "Wiki link `À propos` does not match any page or section");
+    }
 
     #[test]
     fn can_resolve_valid_internal_link() {
@@ -111,6 +111,12 @@ pub fn render_template(
             name,
             "https://www.getzola.org/documentation/templates/pages-sections/#page-variables"
         ),
+        "author.html" => {
+            render_default_tpl!(name, "https://www.getzola.org/documentation/templates/authors/")
+        }
+        "archive.html" => {
+            render_default_tpl!(name, "https://www.getzola.org/documentation/templates/archive/")
+        }
         "single.html" | "list.html" => {
             render_default_tpl!(name, "https://www.getzola.org/documentation/templates/taxonomies/")
         }
@@ -14,3 +14,15 @@ impl InsertAnchor {
         matches!(self, InsertAnchor::Left | InsertAnchor::Right)
     }
 }
+
+/// How to generate a summary for pages without a `<!-- more -->` marker
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AutoSummary {
+    /// Only pages with a `<!-- more -->` marker have a summary
+    None,
+    /// The first paragraph
+    Paragraph,
+    /// The first N words
+    Words(usize),
+}
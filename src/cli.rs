@@ -111,6 +111,9 @@ pub enum Command {
         /// Skip external links
         #[clap(long)]
         skip_external_links: bool,
+        /// Check the spelling of the content with the Hunspell dictionaries of the site
+        #[clap(long)]
+        spelling: bool,
     },
 
     /// Generate shell completion
@@ -12,6 +12,7 @@ pub fn check(
     base_url: Option<&str>,
     include_drafts: bool,
     skip_external_links: bool,
+    spelling: bool,
 ) -> Result<()> {
     let bp = base_path.map(PathBuf::from).unwrap_or_else(|| PathBuf::from(root_dir));
     let mut site = Site::new(bp, config_file)?;
@@ -26,6 +27,9 @@ pub fn check(
     if skip_external_links {
         site.skip_external_links_check();
     }
+    if spelling {
+        site.enable_spellcheck();
+    }
     site.load()?;
     messages::check_site_summary(&site);
     messages::warn_about_ignored_pages(&site);